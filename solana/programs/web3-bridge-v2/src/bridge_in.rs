@@ -1,10 +1,10 @@
-// pub mod events;
+pub mod events;
 pub mod instructions;
 pub mod structs;
 pub mod states;
 
 pub use {
-    // events::*,
+    events::*,
     instructions::*,
     structs::*,
     states::*,
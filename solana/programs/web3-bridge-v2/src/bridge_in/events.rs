@@ -0,0 +1,75 @@
+use {
+    crate::bridge_in::VaultReserve,
+    anchor_lang::prelude::*,
+};
+
+#[event]
+pub struct VaultCreated {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub token_program: Pubkey,
+}
+
+#[event]
+pub struct LiquidityDeposited {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub from: Pubkey,
+    pub amount: u64,
+    pub liquidity: u64,
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub liquidity: u64,
+}
+
+#[event]
+pub struct LpDeposited {
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub lp_liquidity: u64,
+}
+
+#[event]
+pub struct LpWithdrawn {
+    pub mint: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub lp_liquidity: u64,
+}
+
+#[event]
+pub struct ReservesReported {
+    pub reserves: Vec<VaultReserve>,
+}
+
+#[event]
+pub struct CctpReceived {
+    pub source_domain: u32,
+    pub nonce: [u8; 32],
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub to: Pubkey,
+    pub to_token: Pubkey,
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct WormholeRedeemed {
+    pub vaa_hash: [u8; 32],
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub to: Pubkey,
+    pub to_token: Pubkey,
+    pub amount_out: u64,
+}
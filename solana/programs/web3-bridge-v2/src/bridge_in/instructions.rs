@@ -1,23 +1,51 @@
 pub mod accept_ownership;
 pub mod claim;
+pub mod create_vault;
+pub mod deposit_liquidity;
 pub mod initialize;
+pub mod lp_deposit;
+pub mod lp_withdraw;
+pub mod migrate_message_state;
 pub mod pause;
+pub mod receive_cctp;
+pub mod redeem_wormhole;
 pub mod refund;
+pub mod report_reserves;
+pub mod self_refund;
+pub mod set_gasrefund;
+pub mod set_gasrefund_fee_cap;
+pub mod set_lp_fee;
 pub mod set_mpc;
 pub mod set_oracle;
+pub mod set_self_refund_delay;
 pub mod transfer_ownership;
 pub mod unpause;
 pub mod verify;
+pub mod withdraw_liquidity;
 
 pub use {
     accept_ownership::*,
     claim::*,
+    create_vault::*,
+    deposit_liquidity::*,
     initialize::*,
+    lp_deposit::*,
+    lp_withdraw::*,
+    migrate_message_state::*,
     pause::*,
+    receive_cctp::*,
+    redeem_wormhole::*,
     refund::*,
+    report_reserves::*,
+    self_refund::*,
+    set_gasrefund::*,
+    set_gasrefund_fee_cap::*,
+    set_lp_fee::*,
     set_mpc::*,
     set_oracle::*,
+    set_self_refund_delay::*,
     transfer_ownership::*,
     unpause::*,
     verify::*,
+    withdraw_liquidity::*,
 };
\ No newline at end of file
@@ -1,8 +1,9 @@
 use {
     crate::{
         bridge_in::{
-            BridgeMessage, ContractConfig, ToSwapMessageState
+            BridgeMessage, ContractConfig, GasRefundConfig, GasRefundLedger, ToSwapMessageState, VaultState
         },
+        bridge_out::{CommissionPaid, PartnerConfig},
         common::{
             dexrouter_program, 
             wrapped_sol, 
@@ -18,15 +19,18 @@ use {
         associated_token::{get_associated_token_address, get_associated_token_address_with_program_id}, token::{self, spl_token, Transfer}, token_2022::Token2022, token_interface::{Mint, TokenAccount, TokenInterface}
     },
     dex_solana::{cpi::accounts::SwapAccounts, SwapArgs},
+    std::io::Read,
 };
 
 
 #[derive(Accounts)]
+#[instruction(data: ClaimArgs)]
 pub struct Claim<'info> {
     #[account(
+        mut,
         signer
     )]
-    /// The authorized caller.
+    /// The authorized caller, pays for the gas refund ledger of a new source mint.
     /// CHECK: The address authorized to call the claim function.
     pub authorized_caller: Signer<'info>,
 
@@ -38,9 +42,26 @@ pub struct Claim<'info> {
     )]
     pub xbridge_authority: AccountInfo<'info>,
 
-    /// CHECK: gasrefund
+    /// CHECK: gasrefund, pinned by gasrefund_config
     pub gasrefund: AccountInfo<'info>,
 
+    #[account(
+        has_one = gasrefund @ XBridgeErrorCode::InvalidGasRefundAccount,
+        seeds = [b"gasrefund_config"],
+        bump
+    )]
+    pub gasrefund_config: Account<'info, GasRefundConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authorized_caller,
+        space = 8 + GasRefundLedger::INIT_SPACE,
+        seeds = [b"gasrefund_ledger", source_mint.key().as_ref()],
+        bump
+    )]
+    /// Fee cap and ledger of source_mint, created uncapped until set_gasrefund_fee_cap
+    pub gasrefund_ledger: Box<Account<'info, GasRefundLedger>>,
+
     #[account(
         mut,
         token::mint = source_mint,
@@ -48,6 +69,15 @@ pub struct Claim<'info> {
     )]
     pub xbridge_source_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        seeds = [b"vault_state", source_mint.key().as_ref()],
+        bump
+    )]
+    /// Vault state PDA of source_mint, required for every mint with a create_vault vault.
+    /// CHECK: seeds checked, empty while source_mint has no vault, loaded by VaultState::load_for_vault
+    pub vault_state: UncheckedAccount<'info>,
+
     #[account(
         mut,
         token::mint = source_mint,
@@ -94,17 +124,116 @@ pub struct Claim<'info> {
     /// CHECK: fixed
     #[account(address = system_program::id())]
     pub system_program: Program<'info, System>,
+
+    #[account(
+        constraint = partner_config.active @ XBridgeErrorCode::PartnerNotActive,
+        seeds = [b"partner", data.partner_id.unwrap_or_default().to_le_bytes().as_ref()],
+        bump
+    )]
+    /// Optional partner config of data.partner_id, the same registry as bridge-out commission.
+    /// When present, remaining_accounts start with one source_mint token account per recipient,
+    /// in order, followed by the dex accounts.
+    pub partner_config: Option<Box<Account<'info, PartnerConfig>>>,
 }
 
-#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+#[derive(AnchorSerialize, Clone)]
 pub struct ClaimArgs {
     pub create_pda_fee: u64,
     pub dex_swap_args: SwapArgs,
     pub orderid: u128,
+    pub partner_id: Option<u64>,
+}
+
+// claims encoded before partner_id was added end after orderid and read as no partner
+impl AnchorDeserialize for ClaimArgs {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> std::io::Result<Self> {
+        Ok(ClaimArgs {
+            create_pda_fee: u64::deserialize_reader(reader)?,
+            dex_swap_args: SwapArgs::deserialize_reader(reader)?,
+            orderid: u128::deserialize_reader(reader)?,
+            partner_id: read_partner_id(reader)?,
+        })
+    }
+}
+
+fn read_partner_id<R: Read>(reader: &mut R) -> std::io::Result<Option<u64>> {
+    let mut tag = [0u8; 1];
+    if reader.read(&mut tag)? == 0 {
+        return Ok(None);
+    }
+    match tag[0] {
+        0 => Ok(None),
+        1 => Ok(Some(u64::deserialize_reader(reader)?)),
+        _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid partner_id")),
+    }
+}
+
+// commission of each partner recipient, skimmed from from_amount, empty without a partner
+fn claim_commission_amounts(
+    partner_config: &Option<Box<Account<'_, PartnerConfig>>>,
+    partner_id: Option<u64>,
+    from_amount: u64,
+) -> Result<Vec<u64>> {
+    match (partner_id, partner_config) {
+        (None, None) => Ok(Vec::new()),
+        (Some(partner_id), Some(partner_config)) => {
+            require!(
+                partner_config.partner_id == partner_id && partner_config.active,
+                XBridgeErrorCode::PartnerNotActive
+            );
+            partner_config.skim_amounts(from_amount)
+        }
+        _ => err!(XBridgeErrorCode::PartnerNotActive),
+    }
+}
+
+// transfers the commission to the recipient token accounts at the head of remaining_accounts
+fn pay_claim_commission<'info>(
+    _ctx: &Context<'_, '_, 'info, 'info, Claim<'info>>,
+    orderid: u128,
+    from_amount: u64,
+    commission_amounts: &[u64],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let Some(partner_config) = &_ctx.accounts.partner_config else {
+        return Ok(());
+    };
+    require!(
+        _ctx.remaining_accounts.len() >= commission_amounts.len(),
+        XBridgeErrorCode::InvalidCommissionTokenAccount
+    );
+    for (i, recipient) in partner_config.recipients.iter().enumerate() {
+        let commission_token_account: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(&_ctx.remaining_accounts[i])?;
+        require!(
+            commission_token_account.mint == _ctx.accounts.source_mint.key()
+                && commission_token_account.owner == recipient.recipient,
+            XBridgeErrorCode::InvalidCommissionTokenAccount
+        );
+        if commission_amounts[i] > 0 {
+            let cpi_accounts = Transfer {
+                from: _ctx.accounts.xbridge_source_token_account.to_account_info(),
+                to: _ctx.remaining_accounts[i].clone(),
+                authority: _ctx.accounts.xbridge_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(_ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, commission_amounts[i])?;
+        }
+        emit!(CommissionPaid {
+            order_id: orderid.to_string(),
+            adaptor_id: None,
+            partner_id: partner_config.partner_id,
+            recipient: commission_token_account.key(),
+            token: _ctx.accounts.source_mint.key(),
+            rate: recipient.rate,
+            base_amount: from_amount,
+            amount: commission_amounts[i],
+        });
+    }
+    Ok(())
 }
 
 pub fn claim<'info>(
-    _ctx: Context<'_, '_, '_, 'info, Claim<'info>>, 
+    _ctx: Context<'_, '_, 'info, 'info, Claim<'info>>, 
     data: ClaimArgs,
 ) -> Result<()> {
     // toswap_message_request.is_used != true
@@ -125,6 +254,8 @@ pub fn claim<'info>(
         (get_associated_token_address(&to_address, &_ctx.accounts.destination_mint.key()) == _ctx.accounts.destination_token_account.key()) || (get_associated_token_address_with_program_id(&to_address, &_ctx.accounts.destination_mint.key(), &Token2022::id()) == _ctx.accounts.destination_token_account.key()),
         XBridgeErrorCode::InvalidDexSwapArgsToAddress
     );
+    // verify to_token_address == _ctx.accounts.destination_mint, unbound for legacy messages
+    request_src_chain_message.check_to_token(&_ctx.accounts.destination_mint.key())?;
     // verify from_token_address == _ctx.accounts.source_mint
     let from_token_bytes = request_src_chain_message.from_token;
     let from_token_address = Pubkey::from(from_token_bytes);
@@ -132,22 +263,38 @@ pub fn claim<'info>(
         from_token_address == _ctx.accounts.source_mint.key(),
         XBridgeErrorCode::InvalidDexSwapArgsFromTokenAddress
     );
-    // verify from_amount == data.dex_swap_args.amount_in + data.create_pda_fee
+    // verify from_amount == data.dex_swap_args.amount_in + data.create_pda_fee + partner commission
     let from_amount_bytes = &request_src_chain_message.from_amount[24..32];
     let from_amount = u64::from_be_bytes(from_amount_bytes.try_into().expect("slice with incorrect length"));
     let max_amount = from_amount.checked_mul(11).and_then(|v| v.checked_div(10)).expect("Multiplication overflow");
-    let total_amount = data.dex_swap_args.amount_in.checked_add(data.create_pda_fee).expect("Addition overflow");
+    let commission_amounts = claim_commission_amounts(&_ctx.accounts.partner_config, data.partner_id, from_amount)?;
+    let commission_amount = commission_amounts.iter().try_fold(0u64, |acc, v| acc.checked_add(*v)).ok_or(XBridgeErrorCode::CalculationError)?;
+    let total_amount = data.dex_swap_args.amount_in
+        .checked_add(data.create_pda_fee)
+        .and_then(|v| v.checked_add(commission_amount))
+        .expect("Addition overflow");
     require!(
         max_amount >= total_amount,
         XBridgeErrorCode::InvalidDexSwapArgsFromAmount
     );
+    // verify create_pda_fee is within the gas refund cap of source_mint
+    let source_mint_key = _ctx.accounts.source_mint.key();
+    _ctx.accounts.gasrefund_ledger.init_if_new(source_mint_key);
+    _ctx.accounts.gasrefund_ledger.check_fee(data.create_pda_fee, from_amount)?;
+    let mut vault_state = VaultState::load_for_vault(
+        &_ctx.accounts.vault_state,
+        &_ctx.accounts.xbridge_source_token_account.key(),
+    )?;
 
     let authority_seeds: &[&[u8]] = &[b"xbridge_authority_pda", &[_ctx.bumps.xbridge_authority]];
     let signer_seeds = [
             &authority_seeds[..]
     ];
 
-    let dex_remaining_accounts = _ctx.remaining_accounts.to_vec();
+    // skim the partner commission before the swap
+    pay_claim_commission(&_ctx, data.orderid, from_amount, &commission_amounts, &signer_seeds)?;
+
+    let dex_remaining_accounts = _ctx.remaining_accounts[commission_amounts.len()..].to_vec();
     let dex_swap_args = data.dex_swap_args.clone();      
     let dex_swap_ctx = CpiContext::new_with_signer(
         _ctx.accounts.dex_program.to_account_info(), 
@@ -163,8 +310,13 @@ pub fn claim<'info>(
     .with_remaining_accounts(dex_remaining_accounts);
     dex_solana::cpi::swap(dex_swap_ctx, dex_swap_args, 0)?;
 
-    // sending transaction fee to gasrefund ata address
-    if data.create_pda_fee > 0 {
+    // sending transaction fee to gasrefund ata address, the share holders' slice stays in the vault
+    let lp_fee = match &mut vault_state {
+        Some(vault_state) => vault_state.accrue_lp_fee(data.create_pda_fee)?,
+        None => 0,
+    };
+    let gasrefund_fee = data.create_pda_fee.checked_sub(lp_fee).ok_or(XBridgeErrorCode::CalculationError)?;
+    if gasrefund_fee > 0 {
         let token_program = _ctx.accounts.token_program.to_account_info();
         let xbridge_source_token_account = _ctx.accounts.xbridge_source_token_account.to_account_info();
         let gasrefund_token_account = _ctx.accounts.gasrefund_source_token_account.to_account_info();
@@ -174,16 +326,24 @@ pub fn claim<'info>(
             authority: _ctx.accounts.xbridge_authority.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, &signer_seeds);
-        token::transfer(cpi_ctx, data.create_pda_fee)?;
+        token::transfer(cpi_ctx, gasrefund_fee)?;
     }
+    _ctx.accounts.gasrefund_ledger.record_fee(gasrefund_fee)?;
 
-    toswap_message_request.is_used = true;
+    if let Some(vault_state) = &mut vault_state {
+        vault_state.record_claim(from_amount, data.dex_swap_args.amount_in)?;
+        // the payout is backed by owner and share holders' liquidity pro rata
+        _ctx.accounts.xbridge_source_token_account.reload()?;
+        vault_state.mark_to_vault(_ctx.accounts.xbridge_source_token_account.amount)?;
+        vault_state.store(&_ctx.accounts.vault_state)?;
+    }
+    _ctx.accounts.toswap_message_request.is_used = true;
 
     Ok(())
 }
 
 pub fn claim_to_sol<'info>(
-    _ctx: Context<'_, '_, '_, 'info, Claim<'info>>, 
+    _ctx: Context<'_, '_, 'info, 'info, Claim<'info>>, 
     data: ClaimArgs,
 ) -> Result<()> {
     // toswap_message_request.is_used != true
@@ -213,6 +373,12 @@ pub fn claim_to_sol<'info>(
         token_account.amount == 0,          // The WSOL SPL-PDA must be a new account with a balance of 0
         XBridgeErrorCode::WsolPdaInvalidAccountAmount
     );
+    // verify to_token_address == wsol == _ctx.accounts.destination_mint, unbound for legacy messages
+    require!(
+        _ctx.accounts.destination_mint.key() == wrapped_sol::ID,
+        XBridgeErrorCode::DestinationMintMismatch
+    );
+    request_src_chain_message.check_to_token(&wrapped_sol::ID)?;
     // verify from_token_address == _ctx.accounts.source_mint
     let from_token_bytes = request_src_chain_message.from_token;
     let from_token_address = Pubkey::from(from_token_bytes);
@@ -220,22 +386,38 @@ pub fn claim_to_sol<'info>(
         from_token_address == _ctx.accounts.source_mint.key(),
         XBridgeErrorCode::InvalidDexSwapArgsFromTokenAddress
     );
-    // verify from_amount == data.dex_swap_args.amount_in + data.create_pda_fee
+    // verify from_amount == data.dex_swap_args.amount_in + data.create_pda_fee + partner commission
     let from_amount_bytes = &request_src_chain_message.from_amount[24..32];
     let from_amount = u64::from_be_bytes(from_amount_bytes.try_into().expect("slice with incorrect length"));
     let max_amount = from_amount.checked_mul(11).and_then(|v| v.checked_div(10)).expect("Multiplication overflow");
-    let total_amount = data.dex_swap_args.amount_in.checked_add(data.create_pda_fee).expect("Addition overflow");
+    let commission_amounts = claim_commission_amounts(&_ctx.accounts.partner_config, data.partner_id, from_amount)?;
+    let commission_amount = commission_amounts.iter().try_fold(0u64, |acc, v| acc.checked_add(*v)).ok_or(XBridgeErrorCode::CalculationError)?;
+    let total_amount = data.dex_swap_args.amount_in
+        .checked_add(data.create_pda_fee)
+        .and_then(|v| v.checked_add(commission_amount))
+        .expect("Addition overflow");
     require!(
         max_amount >= total_amount,
         XBridgeErrorCode::InvalidDexSwapArgsFromAmount
     );
+    // verify create_pda_fee is within the gas refund cap of source_mint
+    let source_mint_key = _ctx.accounts.source_mint.key();
+    _ctx.accounts.gasrefund_ledger.init_if_new(source_mint_key);
+    _ctx.accounts.gasrefund_ledger.check_fee(data.create_pda_fee, from_amount)?;
+    let mut vault_state = VaultState::load_for_vault(
+        &_ctx.accounts.vault_state,
+        &_ctx.accounts.xbridge_source_token_account.key(),
+    )?;
 
     let authority_seeds: &[&[u8]] = &[b"xbridge_authority_pda", &[_ctx.bumps.xbridge_authority]];
     let signer_seeds = [
             &authority_seeds[..]
     ];
 
-    let dex_remaining_accounts = _ctx.remaining_accounts.to_vec();
+    // skim the partner commission before the swap
+    pay_claim_commission(&_ctx, data.orderid, from_amount, &commission_amounts, &signer_seeds)?;
+
+    let dex_remaining_accounts = _ctx.remaining_accounts[commission_amounts.len()..].to_vec();
     let dex_swap_args = data.dex_swap_args.clone();      
     let dex_swap_ctx = CpiContext::new_with_signer(
         _ctx.accounts.dex_program.to_account_info(), 
@@ -251,8 +433,13 @@ pub fn claim_to_sol<'info>(
     .with_remaining_accounts(dex_remaining_accounts);
     dex_solana::cpi::swap(dex_swap_ctx, dex_swap_args, 0)?;
 
-    // sending transaction fee to gasrefund ata address
-    if data.create_pda_fee > 0 {
+    // sending transaction fee to gasrefund ata address, the share holders' slice stays in the vault
+    let lp_fee = match &mut vault_state {
+        Some(vault_state) => vault_state.accrue_lp_fee(data.create_pda_fee)?,
+        None => 0,
+    };
+    let gasrefund_fee = data.create_pda_fee.checked_sub(lp_fee).ok_or(XBridgeErrorCode::CalculationError)?;
+    if gasrefund_fee > 0 {
         let token_program = _ctx.accounts.token_program.to_account_info();
         let xbridge_source_token_account = _ctx.accounts.xbridge_source_token_account.to_account_info();
         let gasrefund_token_account = _ctx.accounts.gasrefund_source_token_account.to_account_info();
@@ -262,10 +449,37 @@ pub fn claim_to_sol<'info>(
             authority: _ctx.accounts.xbridge_authority.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, &signer_seeds);
-        token::transfer(cpi_ctx, data.create_pda_fee)?;
+        token::transfer(cpi_ctx, gasrefund_fee)?;
     }
+    _ctx.accounts.gasrefund_ledger.record_fee(gasrefund_fee)?;
 
-    toswap_message_request.is_used = true;
+    if let Some(vault_state) = &mut vault_state {
+        vault_state.record_claim(from_amount, data.dex_swap_args.amount_in)?;
+        // the payout is backed by owner and share holders' liquidity pro rata
+        _ctx.accounts.xbridge_source_token_account.reload()?;
+        vault_state.mark_to_vault(_ctx.accounts.xbridge_source_token_account.amount)?;
+        vault_state.store(&_ctx.accounts.vault_state)?;
+    }
+    _ctx.accounts.toswap_message_request.is_used = true;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn legacy_partner_id() {
+        // a claim encoded before partner_id was added
+        assert_eq!(read_partner_id(&mut &[][..]).unwrap(), None);
+        assert_eq!(read_partner_id(&mut &[0u8][..]).unwrap(), None);
+        let mut data = vec![1u8];
+        data.extend_from_slice(&7u64.to_le_bytes());
+        assert_eq!(read_partner_id(&mut &data[..]).unwrap(), Some(7));
+        assert!(read_partner_id(&mut &[2u8][..]).is_err());
+        assert!(read_partner_id(&mut &data[..5]).is_err());
+        // the encoding of ClaimArgs.partner_id
+        assert_eq!(Some(7u64).try_to_vec().unwrap(), data);
+    }
+}
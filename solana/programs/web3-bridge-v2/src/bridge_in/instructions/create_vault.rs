@@ -0,0 +1,107 @@
+use {
+    crate::bridge_in::{ContractConfig, VaultCreated, VaultState},
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+};
+
+
+#[derive(Accounts)]
+pub struct CreateVaultContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    /// The authority PDA derived from xbridge_program
+    /// CHECK: This account is the authority derived from the xbridge_program.
+    #[account(
+        seeds = [b"xbridge_authority_pda"],
+        bump,
+    )]
+    pub xbridge_authority: AccountInfo<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = xbridge_authority,
+        associated_token::token_program = token_program,
+    )]
+    /// Vault Token Account.
+    /// ATA: mint = mint, owner = xbridge_authority,
+    /// This account is used to pay out inbound transfers.
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"vault_share_mint", mint.key().as_ref()],
+        bump,
+        mint::decimals = mint.decimals,
+        mint::authority = xbridge_authority,
+        mint::token_program = token_program,
+    )]
+    /// LP Share Mint.
+    /// PDA: seeds = [b"vault_share_mint", mint], mint authority = xbridge_authority,
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // vaultState state account, one per mint
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + VaultState::INIT_SPACE,
+        seeds = [b"vault_state", mint.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    /// SPL Token Program or SPL Token 2022 Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" or "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+    /// CHECK: fixed
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated Token Program
+    /// "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+    /// CHECK: fixed
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_vault(ctx: Context<CreateVaultContext>) -> Result<()> {
+    let vault_state = &mut ctx.accounts.vault_state;
+
+    vault_state.mint = ctx.accounts.mint.key();
+    vault_state.vault = ctx.accounts.vault.key();
+    vault_state.token_program = ctx.accounts.token_program.key();
+    // liquidity that was funded to the vault outside of deposit_liquidity is taken over as is
+    vault_state.liquidity = ctx.accounts.vault.amount;
+    vault_state.total_deposited = 0;
+    vault_state.total_withdrawn = 0;
+    vault_state.share_mint = ctx.accounts.share_mint.key();
+    vault_state.lp_liquidity = 0;
+    vault_state.lp_fee_bps = 0;
+
+    emit!(VaultCreated {
+        mint: vault_state.mint,
+        vault: vault_state.vault,
+        token_program: vault_state.token_program,
+    });
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use {
+    crate::{
+        bridge_in::{ContractConfig, LiquidityDeposited, VaultState},
+        common::XBridgeErrorCode,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+
+#[derive(Accounts)]
+pub struct DepositLiquidityContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = mint @ XBridgeErrorCode::InvalidAccount,
+        has_one = vault @ XBridgeErrorCode::InvalidAccount,
+        has_one = token_program @ XBridgeErrorCode::InvalidAccount,
+        seeds = [b"vault_state", mint.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token Program or SPL Token 2022 Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" or "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+    /// CHECK: fixed
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct DepositLiquidityParams {
+    pub amount: u64,
+}
+
+pub fn deposit_liquidity(ctx: Context<DepositLiquidityContext>, data: DepositLiquidityParams) -> Result<()> {
+    require!(data.amount > 0, XBridgeErrorCode::InvalidLiquidityAmount);
+
+    // payouts and replenishments since the last mark move both sides pro rata
+    let vault_amount = ctx.accounts.vault.amount;
+    ctx.accounts.vault_state.mark_to_vault(vault_amount)?;
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.owner_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, data.amount, ctx.accounts.mint.decimals)?;
+
+    let vault_state = &mut ctx.accounts.vault_state;
+    vault_state.liquidity = vault_state.liquidity
+        .checked_add(data.amount)
+        .ok_or(XBridgeErrorCode::CalculationError)?;
+    vault_state.total_deposited = vault_state.total_deposited
+        .checked_add(data.amount)
+        .ok_or(XBridgeErrorCode::CalculationError)?;
+
+    emit!(LiquidityDeposited {
+        mint: vault_state.mint,
+        vault: vault_state.vault,
+        from: ctx.accounts.owner_token_account.key(),
+        amount: data.amount,
+        liquidity: vault_state.liquidity,
+    });
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use {
+    crate::{
+        bridge_in::{ContractConfig, LpDeposited, VaultState},
+        common::XBridgeErrorCode,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked},
+    },
+};
+
+
+#[derive(Accounts)]
+pub struct LpDeposit<'info> {
+    #[account(mut)]
+    /// Liquidity provider.
+    pub provider: Signer<'info>,
+
+    /// The authority PDA derived from xbridge_program
+    /// CHECK: This account is the authority derived from the xbridge_program.
+    #[account(
+        seeds = [b"xbridge_authority_pda"],
+        bump,
+    )]
+    pub xbridge_authority: AccountInfo<'info>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = mint @ XBridgeErrorCode::InvalidAccount,
+        has_one = vault @ XBridgeErrorCode::InvalidAccount,
+        has_one = share_mint @ XBridgeErrorCode::InvalidAccount,
+        has_one = token_program @ XBridgeErrorCode::InvalidAccount,
+        seeds = [b"vault_state", mint.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Box<Account<'info, VaultState>>,
+
+    #[account(mut)]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = provider,
+    )]
+    pub provider_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = provider,
+        associated_token::mint = share_mint,
+        associated_token::authority = provider,
+        associated_token::token_program = token_program,
+    )]
+    /// Provider Share Account.
+    /// ATA: mint = share_mint, owner = provider,
+    pub provider_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = !contract_config.paused @ XBridgeErrorCode::AlreadyPaused,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    /// SPL Token Program or SPL Token 2022 Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" or "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+    /// CHECK: fixed
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated Token Program
+    /// "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+    /// CHECK: fixed
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct LpDepositArgs {
+    pub amount: u64,
+    pub min_shares: u64,
+}
+
+pub fn lp_deposit(ctx: Context<LpDeposit>, data: LpDepositArgs) -> Result<()> {
+    require!(data.amount > 0, XBridgeErrorCode::InvalidLiquidityAmount);
+
+    // payouts and replenishments since the last mark move both sides pro rata
+    let vault_amount = ctx.accounts.vault.amount;
+    ctx.accounts.vault_state.mark_to_vault(vault_amount)?;
+
+    let shares = ctx.accounts.vault_state.shares_for_deposit(data.amount, ctx.accounts.share_mint.supply)?;
+    require!(
+        shares > 0 && shares >= data.min_shares,
+        XBridgeErrorCode::InvalidLiquidityAmount
+    );
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.provider_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.provider.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, data.amount, ctx.accounts.mint.decimals)?;
+
+    let authority_seeds: &[&[u8]] = &[b"xbridge_authority_pda", &[ctx.bumps.xbridge_authority]];
+    let signer_seeds = [
+            &authority_seeds[..]
+    ];
+    let cpi_accounts_mint = MintTo {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.provider_share_account.to_account_info(),
+        authority: ctx.accounts.xbridge_authority.to_account_info(),
+    };
+    let cpi_ctx_mint = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts_mint, &signer_seeds);
+    token_interface::mint_to(cpi_ctx_mint, shares)?;
+
+    let vault_state = &mut ctx.accounts.vault_state;
+    vault_state.lp_liquidity = vault_state.lp_liquidity
+        .checked_add(data.amount)
+        .ok_or(XBridgeErrorCode::CalculationError)?;
+
+    emit!(LpDeposited {
+        mint: vault_state.mint,
+        provider: ctx.accounts.provider.key(),
+        amount: data.amount,
+        shares,
+        lp_liquidity: vault_state.lp_liquidity,
+    });
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use {
+    crate::{
+        bridge_in::{ContractConfig, LpWithdrawn, VaultState},
+        common::XBridgeErrorCode,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+
+#[derive(Accounts)]
+pub struct LpWithdraw<'info> {
+    #[account(mut)]
+    /// Liquidity provider.
+    pub provider: Signer<'info>,
+
+    /// The authority PDA derived from xbridge_program
+    /// CHECK: This account is the authority derived from the xbridge_program.
+    #[account(
+        seeds = [b"xbridge_authority_pda"],
+        bump,
+    )]
+    pub xbridge_authority: AccountInfo<'info>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        has_one = mint @ XBridgeErrorCode::InvalidAccount,
+        has_one = vault @ XBridgeErrorCode::InvalidAccount,
+        has_one = share_mint @ XBridgeErrorCode::InvalidAccount,
+        has_one = token_program @ XBridgeErrorCode::InvalidAccount,
+        seeds = [b"vault_state", mint.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Box<Account<'info, VaultState>>,
+
+    #[account(mut)]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub provider_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = provider,
+    )]
+    pub provider_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = !contract_config.paused @ XBridgeErrorCode::AlreadyPaused,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    /// SPL Token Program or SPL Token 2022 Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" or "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+    /// CHECK: fixed
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct LpWithdrawArgs {
+    pub shares: u64,
+    pub min_amount: u64,
+}
+
+pub fn lp_withdraw(ctx: Context<LpWithdraw>, data: LpWithdrawArgs) -> Result<()> {
+    require!(data.shares > 0, XBridgeErrorCode::InvalidLiquidityAmount);
+
+    // payouts and replenishments since the last mark move both sides pro rata
+    let vault_amount = ctx.accounts.vault.amount;
+    ctx.accounts.vault_state.mark_to_vault(vault_amount)?;
+
+    let amount = ctx.accounts.vault_state.amount_for_shares(data.shares, ctx.accounts.share_mint.supply)?;
+    require!(
+        amount >= data.min_amount,
+        XBridgeErrorCode::InvalidLiquidityAmount
+    );
+    // share holders can only withdraw what the vault currently holds
+    require!(
+        amount <= ctx.accounts.vault.amount,
+        XBridgeErrorCode::InsufficientVaultLiquidity
+    );
+
+    let cpi_accounts_burn = Burn {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        from: ctx.accounts.provider_share_account.to_account_info(),
+        authority: ctx.accounts.provider.to_account_info(),
+    };
+    let cpi_ctx_burn = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts_burn);
+    token_interface::burn(cpi_ctx_burn, data.shares)?;
+
+    let authority_seeds: &[&[u8]] = &[b"xbridge_authority_pda", &[ctx.bumps.xbridge_authority]];
+    let signer_seeds = [
+            &authority_seeds[..]
+    ];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.provider_token_account.to_account_info(),
+        authority: ctx.accounts.xbridge_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, &signer_seeds);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    let vault_state = &mut ctx.accounts.vault_state;
+    vault_state.lp_liquidity = vault_state.lp_liquidity
+        .checked_sub(amount)
+        .ok_or(XBridgeErrorCode::CalculationError)?;
+
+    emit!(LpWithdrawn {
+        mint: vault_state.mint,
+        provider: ctx.accounts.provider.key(),
+        amount,
+        shares: data.shares,
+        lp_liquidity: vault_state.lp_liquidity,
+    });
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use {
+    crate::{
+        bridge_in::ToSwapMessageState,
+        common::{init_or_realloc_pda, XBridgeErrorCode},
+    },
+    anchor_lang::{prelude::*, Discriminator},
+};
+
+#[derive(Accounts)]
+pub struct MigrateMessageState<'info> {
+    #[account(mut)]
+    /// Payer.
+    /// Pays the rent of the grown account.
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        owner = crate::ID @ XBridgeErrorCode::InvalidAccount,
+    )]
+    /// ToSwapMessageState created before to_token and refund_to were added.
+    /// CHECK: owner, discriminator and legacy size checked in migrate_message_state
+    pub toswap_message_request: UncheckedAccount<'info>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a legacy ToSwapMessageState in place so claim and refund can load it without a re-verify.
+/// The padded to_token and refund_to are zero, the destination mint stays unbound and refunds go to `to`.
+pub fn migrate_message_state(ctx: Context<MigrateMessageState>) -> Result<()> {
+    let toswap_message_info = ctx.accounts.toswap_message_request.to_account_info();
+    {
+        let data = toswap_message_info.try_borrow_data()?;
+        require!(
+            data.len() == 8 + ToSwapMessageState::LEGACY_SPACE
+                && data[..8] == ToSwapMessageState::DISCRIMINATOR,
+            XBridgeErrorCode::InvalidAccount
+        );
+    }
+
+    init_or_realloc_pda(
+        &ctx.accounts.payer.to_account_info(),
+        &toswap_message_info,
+        &ctx.accounts.system_program.to_account_info(),
+        8 + ToSwapMessageState::INIT_SPACE,
+        ctx.program_id,
+        &[],
+    )?;
+
+    msg!("Migrated toswap_message_request: {}", toswap_message_info.key());
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use {
+    crate::{
+        bridge_in::{CctpBurnMessage, CctpReceived, ContractConfig, SwapPayload},
+        bridge_out::check_pda,
+        common::{
+            cctp_v2_message_program as CCTPV2Message,
+            cctp_v2_program as CCTPV2,
+            dexrouter_program,
+            XBridgeErrorCode,
+            CCTP_SOLANA_DOMAIN,
+        },
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{instruction::Instruction, program::invoke_signed, system_program},
+    },
+    anchor_spl::{
+        associated_token::{get_associated_token_address, get_associated_token_address_with_program_id},
+        token::{self, spl_token, Transfer},
+        token_2022::Token2022,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+    dex_solana::{cpi::accounts::SwapAccounts, SwapArgs},
+};
+
+// remaining_accounts read by receive_message, the dex accounts follow
+pub const CCTP_RECEIVE_ACCOUNTS_LEN: usize = 12;
+
+#[derive(Accounts)]
+pub struct ReceiveCctp<'info> {
+    #[account(mut)]
+    /// The relayer.
+    /// Anyone may relay an attested message, it pays the used_nonce rent.
+    pub payer: Signer<'info>,
+
+    /// The authority PDA derived from xbridge_program
+    /// It is the destination_caller of the burn, only this program can receive the message.
+    /// CHECK: This account is the authority derived from the xbridge_program.
+    #[account(
+        seeds = [b"xbridge_authority_pda"],
+        bump,
+    )]
+    pub xbridge_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::authority = xbridge_authority,
+    )]
+    /// The mint_recipient of the burn, the received amount is swapped out in the same instruction.
+    pub xbridge_source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = destination_mint,
+        constraint = (destination_token_account.to_account_info().owner == &spl_token::id()) || (destination_token_account.to_account_info().owner == &Token2022::id()) @ XBridgeErrorCode::AccountOwnedByWrongProgram
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub source_mint: InterfaceAccount<'info, Mint>,
+
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = dexrouter_program::ID)]
+    /// CHECK: dex_program
+    pub dex_program: AccountInfo<'info>,
+
+    /// message_transmitter_v2 Program ID
+    /// "CCTPV2Sm4AdWt5296sk4P1tnNmRvzJ3CiEodH5uSFe9K"
+    /// CHECK: fixed
+    #[account(address = CCTPV2Message::id())]
+    pub message_transmitter_program: AccountInfo<'info>,
+
+    /// token_messenger_minter_v2 Program ID
+    /// "CCTPV2vPZJS2u2BBsUoscuikbYjnpFmbFsvVuJdgUMQe"
+    /// CHECK: fixed
+    #[account(address = CCTPV2::id())]
+    pub token_messenger_minter_program: AccountInfo<'info>,
+
+    #[account(
+        constraint = !contract_config.paused @ XBridgeErrorCode::AlreadyPaused,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    /// SPL Token Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    /// CHECK: fixed
+    #[account(address = spl_token::id())]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    #[account(address = system_program::id())]
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct ReceiveCctpArgs {
+    pub message: Vec<u8>,
+    pub attestation: Vec<u8>,
+    pub dex_swap_args: SwapArgs,
+}
+
+// checks the CCTP accounts at the head of remaining_accounts
+fn validate_receive_accounts(
+    accounts: &[AccountInfo],
+    burn_message: &CctpBurnMessage,
+    source_mint: &Pubkey,
+) -> Result<()> {
+    require!(
+        accounts.len() >= CCTP_RECEIVE_ACCOUNTS_LEN,
+        XBridgeErrorCode::InvalidAccountsLength
+    );
+    let message_transmitter = CCTPV2Message::id();
+    let token_messenger_minter = CCTPV2::id();
+    let source_domain = burn_message.source_domain.to_string();
+    // authority_pda, PDA: seeds = [b"message_transmitter_authority", token_messenger_minter_program]
+    check_pda(&accounts[0], &[b"message_transmitter_authority", token_messenger_minter.as_ref()], &message_transmitter)?;
+    // message_transmitter, PDA: seeds = [b"message_transmitter"]
+    check_pda(&accounts[1], &[b"message_transmitter"], &message_transmitter)?;
+    // accounts[2] used_nonce, initialized and checked by the message transmitter
+    // event_authority, PDA: seeds = [b"__event_authority"], message_transmitter_program
+    check_pda(&accounts[3], &[b"__event_authority"], &message_transmitter)?;
+    // token_messenger, PDA: seeds = [b"token_messenger"]
+    check_pda(&accounts[4], &[b"token_messenger"], &token_messenger_minter)?;
+    // remote_token_messenger, PDA: seeds = [b"remote_token_messenger", source_domain]
+    check_pda(&accounts[5], &[b"remote_token_messenger", source_domain.as_bytes()], &token_messenger_minter)?;
+    // token_minter, PDA: seeds = [b"token_minter"]
+    check_pda(&accounts[6], &[b"token_minter"], &token_messenger_minter)?;
+    // local_token, PDA: seeds = [b"local_token", mint]
+    check_pda(&accounts[7], &[b"local_token", source_mint.as_ref()], &token_messenger_minter)?;
+    // token_pair, PDA: seeds = [b"token_pair", source_domain, burn_token]
+    check_pda(&accounts[8], &[b"token_pair", source_domain.as_bytes(), &burn_message.burn_token], &token_messenger_minter)?;
+    // accounts[9] fee_recipient_token_account, checked by the token messenger minter
+    // custody_token_account, PDA: seeds = [b"custody", mint]
+    check_pda(&accounts[10], &[b"custody", source_mint.as_ref()], &token_messenger_minter)?;
+    // event_authority, PDA: seeds = [b"__event_authority"], token_messenger_minter_program
+    check_pda(&accounts[11], &[b"__event_authority"], &token_messenger_minter)
+}
+
+pub fn receive_cctp<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReceiveCctp<'info>>,
+    data: ReceiveCctpArgs,
+) -> Result<()> {
+    // the attested message decides the recipient, the token and the minimum it receives
+    let burn_message = CctpBurnMessage::from_message(&data.message)?;
+    let hook_data = SwapPayload::from_payload(&burn_message.hook_data)?;
+    require!(
+        burn_message.destination_domain == CCTP_SOLANA_DOMAIN,
+        XBridgeErrorCode::InvalidCctpMessage
+    );
+    require!(
+        burn_message.destination_caller == ctx.accounts.xbridge_authority.key(),
+        XBridgeErrorCode::CctpDestinationCallerMismatch
+    );
+    require!(
+        burn_message.mint_recipient == ctx.accounts.xbridge_source_token_account.key(),
+        XBridgeErrorCode::InvalidAccount
+    );
+    // verify (hook_data.to & destination_mint) == ctx.accounts.destination_token_account
+    require!(
+        // spl-token or spl-2022-token
+        (get_associated_token_address(&hook_data.to, &ctx.accounts.destination_mint.key()) == ctx.accounts.destination_token_account.key()) || (get_associated_token_address_with_program_id(&hook_data.to, &ctx.accounts.destination_mint.key(), &Token2022::id()) == ctx.accounts.destination_token_account.key()),
+        XBridgeErrorCode::InvalidDexSwapArgsToAddress
+    );
+    require!(
+        hook_data.to_token == ctx.accounts.destination_mint.key(),
+        XBridgeErrorCode::DestinationMintMismatch
+    );
+    validate_receive_accounts(ctx.remaining_accounts, &burn_message, &ctx.accounts.source_mint.key())?;
+
+    let authority_seeds: &[&[u8]] = &[b"xbridge_authority_pda", &[ctx.bumps.xbridge_authority]];
+    let signer_seeds = [
+            &authority_seeds[..]
+    ];
+
+    // receive_message, mints the burned amount less fee_executed to xbridge_source_token_account
+    let accounts = ctx.remaining_accounts;
+    let mut receive_data = vec![38u8, 144u8, 127u8, 225u8, 31u8, 225u8, 238u8, 25u8];
+    receive_data.extend_from_slice(&data.message.try_to_vec()?);
+    receive_data.extend_from_slice(&data.attestation.try_to_vec()?);
+    let ix = Instruction {
+        program_id: CCTPV2Message::id(),
+        data: receive_data,
+        accounts: vec![
+            AccountMeta::new(ctx.accounts.payer.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.xbridge_authority.key(), true),
+            AccountMeta::new_readonly(accounts[0].key(), false),
+            AccountMeta::new_readonly(accounts[1].key(), false),
+            AccountMeta::new(accounts[2].key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_messenger_minter_program.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+            AccountMeta::new_readonly(accounts[3].key(), false),
+            AccountMeta::new_readonly(ctx.accounts.message_transmitter_program.key(), false),
+            // handle_receive_finalized_message / handle_receive_unfinalized_message accounts
+            AccountMeta::new_readonly(accounts[4].key(), false),
+            AccountMeta::new_readonly(accounts[5].key(), false),
+            AccountMeta::new(accounts[6].key(), false),
+            AccountMeta::new(accounts[7].key(), false),
+            AccountMeta::new_readonly(accounts[8].key(), false),
+            AccountMeta::new(accounts[9].key(), false),
+            AccountMeta::new(ctx.accounts.xbridge_source_token_account.key(), false),
+            AccountMeta::new(accounts[10].key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            AccountMeta::new_readonly(accounts[11].key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_messenger_minter_program.key(), false),
+        ],
+    };
+    let mut account_infos = vec![
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.xbridge_authority.to_account_info(),
+        ctx.accounts.xbridge_source_token_account.to_account_info(),
+        ctx.accounts.token_messenger_minter_program.to_account_info(),
+        ctx.accounts.message_transmitter_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    ];
+    account_infos.extend_from_slice(&accounts[..CCTP_RECEIVE_ACCOUNTS_LEN]);
+
+    let mut xbridge_source_token_account = ctx.accounts.xbridge_source_token_account.clone();
+    let before_balance = xbridge_source_token_account.amount;
+    invoke_signed(&ix, &account_infos, &signer_seeds)?;
+    xbridge_source_token_account.reload()?;
+    let received_amount = xbridge_source_token_account.amount.saturating_sub(before_balance);
+    require!(
+        received_amount == burn_message.received_amount()?,
+        XBridgeErrorCode::InvalidCctpMessage
+    );
+
+    // deliver the received amount, swapped to hook_data.to_token unless it is the burned token
+    let mut destination_token_account = ctx.accounts.destination_token_account.clone();
+    let destination_before = destination_token_account.amount;
+    if hook_data.to_token == ctx.accounts.source_mint.key() {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.xbridge_source_token_account.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.xbridge_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, &signer_seeds);
+        token::transfer(cpi_ctx, received_amount)?;
+    } else {
+        require!(
+            data.dex_swap_args.amount_in == received_amount,
+            XBridgeErrorCode::InvalidDexSwapArgsFromAmount
+        );
+        let dex_remaining_accounts = accounts[CCTP_RECEIVE_ACCOUNTS_LEN..].to_vec();
+        let dex_swap_ctx = CpiContext::new_with_signer(
+            ctx.accounts.dex_program.to_account_info(),
+            SwapAccounts {
+                payer: ctx.accounts.xbridge_authority.to_account_info(),
+                source_token_account: ctx.accounts.xbridge_source_token_account.to_account_info(),
+                destination_token_account: ctx.accounts.destination_token_account.to_account_info(),
+                source_mint: ctx.accounts.source_mint.to_account_info(),
+                destination_mint: ctx.accounts.destination_mint.to_account_info(),
+            },
+            &signer_seeds
+        )
+        .with_remaining_accounts(dex_remaining_accounts);
+        dex_solana::cpi::swap(dex_swap_ctx, data.dex_swap_args.clone(), 0)?;
+    }
+    destination_token_account.reload()?;
+    let amount_out = destination_token_account.amount.saturating_sub(destination_before);
+    require!(
+        amount_out >= hook_data.min_return,
+        XBridgeErrorCode::DexMinReturnNotReached
+    );
+
+    emit!(CctpReceived {
+        source_domain: burn_message.source_domain,
+        nonce: burn_message.nonce,
+        mint: ctx.accounts.source_mint.key(),
+        amount: received_amount,
+        to: hook_data.to,
+        to_token: hook_data.to_token,
+        amount_out,
+    });
+    Ok(())
+}
@@ -0,0 +1,313 @@
+use {
+    crate::{
+        bridge_in::{
+            ContractConfig, PostedVaa, SwapPayload, WormholeRedeemState, WormholeRedeemed,
+            WormholeTransferWithPayload,
+        },
+        bridge_out::{check_address, check_pda, check_writable, wormhole::LegacyInstruction},
+        common::{
+            dexrouter_program,
+            wormhole_core_program as Wormhole,
+            wormhole_token_bridge_program as TokenBridge,
+            XBridgeErrorCode,
+            WORMHOLE_SOLANA_CHAIN,
+        },
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{instruction::Instruction, program::invoke_signed, system_program, sysvar},
+    },
+    anchor_spl::{
+        associated_token::{get_associated_token_address, get_associated_token_address_with_program_id},
+        token::{self, spl_token, Transfer},
+        token_2022::Token2022,
+        token_interface::{Mint, TokenAccount, TokenInterface},
+    },
+    dex_solana::{cpi::accounts::SwapAccounts, SwapArgs},
+};
+
+// remaining_accounts read by complete_transfer_with_payload, the dex accounts follow
+pub const WORMHOLE_REDEEM_ACCOUNTS_LEN: usize = 9;
+
+#[derive(Accounts)]
+#[instruction(data: RedeemWormholeArgs)]
+pub struct RedeemWormhole<'info> {
+    #[account(mut)]
+    /// The relayer.
+    /// Anyone may relay a posted VAA, it pays the claim and wormhole_redeem rent.
+    pub payer: Signer<'info>,
+
+    /// The authority PDA derived from xbridge_program
+    /// It is the redeemer of the transfer, only this program can complete it.
+    /// CHECK: This account is the authority derived from the xbridge_program.
+    #[account(
+        seeds = [b"xbridge_authority_pda"],
+        bump,
+    )]
+    pub xbridge_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::authority = xbridge_authority,
+    )]
+    /// Receives the transfer, the received amount is swapped out in the same instruction.
+    pub xbridge_source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = destination_mint,
+        constraint = (destination_token_account.to_account_info().owner == &spl_token::id()) || (destination_token_account.to_account_info().owner == &Token2022::id()) @ XBridgeErrorCode::AccountOwnedByWrongProgram
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub source_mint: InterfaceAccount<'info, Mint>,
+
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        owner = Wormhole::id() @ XBridgeErrorCode::InvalidWormholeVaa,
+        seeds = [b"PostedVAA", data.vaa_hash.as_ref()],
+        bump,
+        seeds::program = Wormhole::id(),
+    )]
+    /// Posted VAA
+    /// PDA: seeds = [b"PostedVAA", vaa_hash], seeds::program = "wormhole_core_program".
+    /// CHECK: owner and seeds are checked, the data is parsed in redeem_wormhole.
+    pub posted_vaa: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + WormholeRedeemState::INIT_SPACE,
+        seeds = [b"wormhole_redeem", data.vaa_hash.as_ref()],
+        bump
+    )]
+    /// Replay protection, one per VAA hash.
+    pub wormhole_redeem: Account<'info, WormholeRedeemState>,
+
+    #[account(address = dexrouter_program::ID)]
+    /// CHECK: dex_program
+    pub dex_program: AccountInfo<'info>,
+
+    #[account(
+        constraint = !contract_config.paused @ XBridgeErrorCode::AlreadyPaused,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    /// SPL Token Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    /// CHECK: fixed
+    #[account(address = spl_token::id())]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    #[account(address = system_program::id())]
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct RedeemWormholeArgs {
+    pub vaa_hash: [u8; 32],
+    pub dex_swap_args: SwapArgs,
+}
+
+// checks the token bridge accounts at the head of remaining_accounts
+fn validate_redeem_accounts(
+    accounts: &[AccountInfo],
+    vaa: &PostedVaa,
+    transfer: &WormholeTransferWithPayload,
+    source_mint: &Pubkey,
+) -> Result<()> {
+    require!(
+        accounts.len() >= WORMHOLE_REDEEM_ACCOUNTS_LEN,
+        XBridgeErrorCode::InvalidAccountsLength
+    );
+    let token_bridge = TokenBridge::id();
+    let emitter_chain = vaa.emitter_chain.to_be_bytes();
+    // config, PDA: seeds = [b"config"]
+    check_pda(&accounts[0], &[b"config"], &token_bridge)?;
+    // claim, PDA: seeds = [emitter_address, emitter_chain, sequence]
+    check_pda(&accounts[1], &[&vaa.emitter_address, &emitter_chain, &vaa.sequence.to_be_bytes()], &token_bridge)?;
+    check_writable(&accounts[1])?;
+    // chain_registration, PDA: seeds = [emitter_chain, emitter_address]
+    check_pda(&accounts[2], &[&emitter_chain, &vaa.emitter_address], &token_bridge)?;
+    // to_fees, a source_mint token account, checked by the token bridge
+    check_writable(&accounts[3])?;
+    if transfer.token_chain == WORMHOLE_SOLANA_CHAIN {
+        // custody, PDA: seeds = [mint]
+        check_pda(&accounts[4], &[source_mint.as_ref()], &token_bridge)?;
+        check_writable(&accounts[4])?;
+        // custody_signer, PDA: seeds = [b"custody_signer"]
+        check_pda(&accounts[5], &[b"custody_signer"], &token_bridge)?;
+    } else {
+        // wrapped_meta, PDA: seeds = [b"meta", mint]
+        check_pda(&accounts[4], &[b"meta", source_mint.as_ref()], &token_bridge)?;
+        // mint_authority, PDA: seeds = [b"mint_signer"]
+        check_pda(&accounts[5], &[b"mint_signer"], &token_bridge)?;
+    }
+    check_address(&accounts[6], &sysvar::rent::id())?;
+    check_address(&accounts[7], &Wormhole::id())?;
+    check_address(&accounts[8], &token_bridge)
+}
+
+pub fn redeem_wormhole<'info>(
+    ctx: Context<'_, '_, '_, 'info, RedeemWormhole<'info>>,
+    data: RedeemWormholeArgs,
+) -> Result<()> {
+    // the posted VAA decides the recipient, the token and the minimum it receives
+    let vaa = PostedVaa::from_account_data(&ctx.accounts.posted_vaa.try_borrow_data()?)?;
+    let transfer = WormholeTransferWithPayload::from_payload(&vaa.payload)?;
+    let swap_payload = SwapPayload::from_payload(&transfer.payload)?;
+    require!(
+        transfer.to_chain == WORMHOLE_SOLANA_CHAIN
+            && transfer.to == ctx.accounts.xbridge_authority.key(),
+        XBridgeErrorCode::InvalidWormholeVaa
+    );
+    // verify source_mint is the native token, or the wrapped mint of (token_chain, token_address)
+    let source_mint = ctx.accounts.source_mint.key();
+    let native = transfer.token_chain == WORMHOLE_SOLANA_CHAIN;
+    if native {
+        require!(
+            transfer.token_address == source_mint.to_bytes(),
+            XBridgeErrorCode::InvalidDexSwapArgsFromTokenAddress
+        );
+    } else {
+        let (wrapped_mint, _) = Pubkey::find_program_address(
+            &[b"wrapped", &transfer.token_chain.to_be_bytes(), &transfer.token_address],
+            &TokenBridge::id(),
+        );
+        require!(
+            wrapped_mint == source_mint,
+            XBridgeErrorCode::InvalidDexSwapArgsFromTokenAddress
+        );
+    }
+    // verify (swap_payload.to & destination_mint) == ctx.accounts.destination_token_account
+    require!(
+        // spl-token or spl-2022-token
+        (get_associated_token_address(&swap_payload.to, &ctx.accounts.destination_mint.key()) == ctx.accounts.destination_token_account.key()) || (get_associated_token_address_with_program_id(&swap_payload.to, &ctx.accounts.destination_mint.key(), &Token2022::id()) == ctx.accounts.destination_token_account.key()),
+        XBridgeErrorCode::InvalidDexSwapArgsToAddress
+    );
+    require!(
+        swap_payload.to_token == ctx.accounts.destination_mint.key(),
+        XBridgeErrorCode::DestinationMintMismatch
+    );
+    validate_redeem_accounts(ctx.remaining_accounts, &vaa, &transfer, &source_mint)?;
+
+    let authority_seeds: &[&[u8]] = &[b"xbridge_authority_pda", &[ctx.bumps.xbridge_authority]];
+    let signer_seeds = [
+            &authority_seeds[..]
+    ];
+
+    // complete_transfer_with_payload, the redeemer xbridge_authority signs as to_owner
+    let accounts = ctx.remaining_accounts;
+    let instruction = if native {
+        LegacyInstruction::CompleteTransferWithPayloadNative
+    } else {
+        LegacyInstruction::CompleteTransferWithPayloadWrapped
+    };
+    let mut ix_accounts = vec![
+        AccountMeta::new(ctx.accounts.payer.key(), true),
+        AccountMeta::new_readonly(accounts[0].key(), false),
+        AccountMeta::new_readonly(ctx.accounts.posted_vaa.key(), false),
+        AccountMeta::new(accounts[1].key(), false),
+        AccountMeta::new_readonly(accounts[2].key(), false),
+        AccountMeta::new(ctx.accounts.xbridge_source_token_account.key(), false),
+        AccountMeta::new_readonly(ctx.accounts.xbridge_authority.key(), true),
+        AccountMeta::new(accounts[3].key(), false),
+    ];
+    if native {
+        ix_accounts.push(AccountMeta::new(accounts[4].key(), false));
+        ix_accounts.push(AccountMeta::new_readonly(source_mint, false));
+    } else {
+        ix_accounts.push(AccountMeta::new(source_mint, false));
+        ix_accounts.push(AccountMeta::new_readonly(accounts[4].key(), false));
+    }
+    ix_accounts.extend_from_slice(&[
+        AccountMeta::new_readonly(accounts[5].key(), false),
+        AccountMeta::new_readonly(accounts[6].key(), false),
+        AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+        AccountMeta::new_readonly(accounts[7].key(), false),
+        AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+    ]);
+    let ix = Instruction {
+        program_id: TokenBridge::id(),
+        data: vec![instruction as u8],
+        accounts: ix_accounts,
+    };
+    let mut account_infos = vec![
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.posted_vaa.to_account_info(),
+        ctx.accounts.xbridge_source_token_account.to_account_info(),
+        ctx.accounts.xbridge_authority.to_account_info(),
+        ctx.accounts.source_mint.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    ];
+    account_infos.extend_from_slice(&accounts[..WORMHOLE_REDEEM_ACCOUNTS_LEN]);
+
+    let mut xbridge_source_token_account = ctx.accounts.xbridge_source_token_account.clone();
+    let before_balance = xbridge_source_token_account.amount;
+    invoke_signed(&ix, &account_infos, &signer_seeds)?;
+    xbridge_source_token_account.reload()?;
+    let received_amount = xbridge_source_token_account.amount.saturating_sub(before_balance);
+
+    // deliver the received amount, swapped to swap_payload.to_token unless it is the bridged token
+    let mut destination_token_account = ctx.accounts.destination_token_account.clone();
+    let destination_before = destination_token_account.amount;
+    if swap_payload.to_token == source_mint {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.xbridge_source_token_account.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: ctx.accounts.xbridge_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, &signer_seeds);
+        token::transfer(cpi_ctx, received_amount)?;
+    } else {
+        require!(
+            data.dex_swap_args.amount_in == received_amount,
+            XBridgeErrorCode::InvalidDexSwapArgsFromAmount
+        );
+        let dex_remaining_accounts = accounts[WORMHOLE_REDEEM_ACCOUNTS_LEN..].to_vec();
+        let dex_swap_ctx = CpiContext::new_with_signer(
+            ctx.accounts.dex_program.to_account_info(),
+            SwapAccounts {
+                payer: ctx.accounts.xbridge_authority.to_account_info(),
+                source_token_account: ctx.accounts.xbridge_source_token_account.to_account_info(),
+                destination_token_account: ctx.accounts.destination_token_account.to_account_info(),
+                source_mint: ctx.accounts.source_mint.to_account_info(),
+                destination_mint: ctx.accounts.destination_mint.to_account_info(),
+            },
+            &signer_seeds
+        )
+        .with_remaining_accounts(dex_remaining_accounts);
+        dex_solana::cpi::swap(dex_swap_ctx, data.dex_swap_args.clone(), 0)?;
+    }
+    destination_token_account.reload()?;
+    let amount_out = destination_token_account.amount.saturating_sub(destination_before);
+    require!(
+        amount_out >= swap_payload.min_return,
+        XBridgeErrorCode::DexMinReturnNotReached
+    );
+
+    let wormhole_redeem = &mut ctx.accounts.wormhole_redeem;
+    wormhole_redeem.vaa_hash = data.vaa_hash;
+    wormhole_redeem.redeemed_at = Clock::get()?.unix_timestamp;
+
+    emit!(WormholeRedeemed {
+        vaa_hash: data.vaa_hash,
+        emitter_chain: vaa.emitter_chain,
+        sequence: vaa.sequence,
+        mint: source_mint,
+        amount: received_amount,
+        to: swap_payload.to,
+        to_token: swap_payload.to_token,
+        amount_out,
+    });
+    Ok(())
+}
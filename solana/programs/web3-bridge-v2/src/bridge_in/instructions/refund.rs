@@ -3,7 +3,10 @@ use {
         bridge_in::{
             ContractConfig,
             BridgeMessage,
+            GasRefundConfig,
+            GasRefundLedger,
             ToSwapMessageState,
+            VaultState,
         },
         common::{
             XBridgeErrorCode,
@@ -18,16 +21,15 @@ use {
         ,
     },
     anchor_spl::{
-        token::{self, spl_token, Transfer},
-        associated_token::get_associated_token_address,
-        token_interface::{TokenInterface, TokenAccount, Mint}
+        associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken, Create},
+        token_interface::{self, TokenInterface, TokenAccount, Mint, TransferChecked}
     },
 };
 
 
 #[derive(Accounts)]
 pub struct Refund<'info> {
-    #[account(signer)]
+    #[account(mut, signer)]
     /// The authorized caller.
     /// CHECK: The address authorized to call the claim function.
     pub authorized_caller: Signer<'info>,
@@ -40,22 +42,49 @@ pub struct Refund<'info> {
     )]
     pub xbridge_authority: AccountInfo<'info>,
 
-    /// CHECK: gasrefund
+    /// CHECK: gasrefund, pinned by gasrefund_config
     pub gasrefund: AccountInfo<'info>,
 
+    #[account(
+        has_one = gasrefund @ XBridgeErrorCode::InvalidGasRefundAccount,
+        seeds = [b"gasrefund_config"],
+        bump
+    )]
+    pub gasrefund_config: Account<'info, GasRefundConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authorized_caller,
+        space = 8 + GasRefundLedger::INIT_SPACE,
+        seeds = [b"gasrefund_ledger", source_mint.key().as_ref()],
+        bump
+    )]
+    /// Fee cap and ledger of source_mint, created uncapped until set_gasrefund_fee_cap
+    pub gasrefund_ledger: Box<Account<'info, GasRefundLedger>>,
+
     #[account(
         mut,
         token::mint = source_mint,
         token::authority = xbridge_authority,
+        token::token_program = token_program,
     )]
     pub xbridge_source_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
-        token::mint = source_mint,
+        seeds = [b"vault_state", source_mint.key().as_ref()],
+        bump
     )]
-    /// CHECK: This is the refund account for the to address's from_token ATA
-    pub refund_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// Vault state PDA of source_mint, required for every mint with a create_vault vault.
+    /// CHECK: seeds checked, empty while source_mint has no vault, loaded by VaultState::load_for_vault
+    pub vault_state: UncheckedAccount<'info>,
+
+    /// CHECK: The refund recipient, verified against BridgeMessage.refund_to (or BridgeMessage.to)
+    pub refund_owner: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: This is the refund_owner's from_token ATA, created idempotently and verified in refund
+    pub refund_token_account: AccountInfo<'info>,
 
     #[account(
         mut,
@@ -64,18 +93,25 @@ pub struct Refund<'info> {
     )]
     pub gasrefund_source_token_account: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        mint::token_program = token_program,
+    )]
     pub source_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
     /// CHECK: 
     pub toswap_message_request: Box<Account<'info, ToSwapMessageState>>,
 
-    /// SPL Token Program
-    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    /// SPL Token Program or SPL Token 2022 Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" or "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
     /// CHECK: fixed
-    #[account(address = spl_token::id())]
     pub token_program: Interface<'info, TokenInterface>,
 
+    /// Associated Token Program
+    /// "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+    /// CHECK: fixed
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
     #[account(
         constraint = authorized_caller.key() == contract_config.mpc ||
                     authorized_caller.key().to_string() == TEST_MPC_STR ||
@@ -115,11 +151,15 @@ pub fn refund<'info>(
     // read toswap_message_request.data
     let request_src_chain_message = BridgeMessage::try_from_slice(&toswap_message_request.data)?;
     BridgeMessage::msg_oracle_data(&request_src_chain_message, data.orderid);
-    // verify (refund_address & source_mint)  == _ctx.accounts.refund_token_account
-    let refund_address_bytes = request_src_chain_message.to;
-    let refund_address = Pubkey::from(refund_address_bytes);
+    // verify refund_owner == refund_to (or to, when no refund_to is signed)
+    let refund_address = request_src_chain_message.refund_address();
     require!(
-        get_associated_token_address(&refund_address, &_ctx.accounts.source_mint.key()) == _ctx.accounts.refund_token_account.key(),
+        refund_address == _ctx.accounts.refund_owner.key(),
+        XBridgeErrorCode::InvalidDexSwapArgsRefundAddress
+    );
+    // verify (refund_address & source_mint) == _ctx.accounts.refund_token_account, spl-token or spl-2022-token
+    require!(
+        get_associated_token_address_with_program_id(&refund_address, &_ctx.accounts.source_mint.key(), &_ctx.accounts.token_program.key()) == _ctx.accounts.refund_token_account.key(),
         XBridgeErrorCode::InvalidDexSwapArgsRefundAddress
     );
     // verify from_token_address == _ctx.accounts.source_mint
@@ -138,6 +178,14 @@ pub fn refund<'info>(
         max_amount >= total_amount,
         XBridgeErrorCode::InvalidDexSwapArgsFromAmount
     );
+    // verify create_pda_fee is within the gas refund cap of source_mint
+    let source_mint_key = _ctx.accounts.source_mint.key();
+    _ctx.accounts.gasrefund_ledger.init_if_new(source_mint_key);
+    _ctx.accounts.gasrefund_ledger.check_fee(data.create_pda_fee, from_amount)?;
+    let mut vault_state = VaultState::load_for_vault(
+        &_ctx.accounts.vault_state,
+        &_ctx.accounts.xbridge_source_token_account.key(),
+    )?;
 
     let authority_seeds: &[&[u8]] = &[b"xbridge_authority_pda", &[_ctx.bumps.xbridge_authority]];
     let signer_seeds = [
@@ -146,30 +194,62 @@ pub fn refund<'info>(
 
     let token_program = _ctx.accounts.token_program.to_account_info();
     let source_token_account = _ctx.accounts.xbridge_source_token_account.to_account_info();
+    let source_mint = _ctx.accounts.source_mint.to_account_info();
+    let decimals = _ctx.accounts.source_mint.decimals;
     // refund 
     if data.refund_amount > 0 {
+        // the refund recipient may not hold the source mint yet
+        associated_token::create_idempotent(
+            CpiContext::new(
+                _ctx.accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: _ctx.accounts.authorized_caller.to_account_info(),
+                    associated_token: _ctx.accounts.refund_token_account.to_account_info(),
+                    authority: _ctx.accounts.refund_owner.to_account_info(),
+                    mint: source_mint.clone(),
+                    system_program: _ctx.accounts.system_program.to_account_info(),
+                    token_program: token_program.clone(),
+                },
+            )
+        )?;
+
         let user_token_account = _ctx.accounts.refund_token_account.to_account_info();
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: source_token_account.clone(),
+            mint: source_mint.clone(),
             to: user_token_account.clone(),
             authority: _ctx.accounts.xbridge_authority.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, &signer_seeds);
-        token::transfer(cpi_ctx.with_signer(&[authority_seeds]), data.refund_amount)?;
+        token_interface::transfer_checked(cpi_ctx, data.refund_amount, decimals)?;
     }
 
-    // sending transaction fee to gasrefund ata address
-    if data.create_pda_fee > 0 {
+    // sending transaction fee to gasrefund ata address, the share holders' slice stays in the vault
+    let lp_fee = match &mut vault_state {
+        Some(vault_state) => vault_state.accrue_lp_fee(data.create_pda_fee)?,
+        None => 0,
+    };
+    let gasrefund_fee = data.create_pda_fee.checked_sub(lp_fee).ok_or(XBridgeErrorCode::CalculationError)?;
+    if gasrefund_fee > 0 {
         let gasrefund_token_account = _ctx.accounts.gasrefund_source_token_account.to_account_info();
-        let cpi_accounts_fee = Transfer {
+        let cpi_accounts_fee = TransferChecked {
             from: source_token_account.clone(),
+            mint: source_mint.clone(),
             to: gasrefund_token_account.clone(),
             authority: _ctx.accounts.xbridge_authority.to_account_info(),
         };
         let cpi_ctx_fee = CpiContext::new_with_signer(token_program.clone(), cpi_accounts_fee, &signer_seeds);
-        token::transfer(cpi_ctx_fee.with_signer(&[authority_seeds]), data.create_pda_fee)?;
+        token_interface::transfer_checked(cpi_ctx_fee, gasrefund_fee, decimals)?;
     }
+    _ctx.accounts.gasrefund_ledger.record_fee(gasrefund_fee)?;
 
+    if let Some(vault_state) = &mut vault_state {
+        vault_state.record_refund(from_amount, data.refund_amount)?;
+        // the payout is backed by owner and share holders' liquidity pro rata
+        _ctx.accounts.xbridge_source_token_account.reload()?;
+        vault_state.mark_to_vault(_ctx.accounts.xbridge_source_token_account.amount)?;
+        vault_state.store(&_ctx.accounts.vault_state)?;
+    }
     toswap_message_request.is_used = true;
 
     Ok(())
@@ -0,0 +1,60 @@
+use {
+    crate::{
+        bridge_in::{ReservesReported, VaultReserve, VaultState},
+        common::XBridgeErrorCode,
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::program::set_return_data,
+    },
+    anchor_spl::token_interface::TokenAccount,
+};
+
+
+#[derive(Accounts)]
+pub struct ReportReserves<'info> {
+    /// The authority PDA derived from xbridge_program
+    /// CHECK: This account is the authority derived from the xbridge_program.
+    #[account(
+        seeds = [b"xbridge_authority_pda"],
+        bump,
+    )]
+    pub xbridge_authority: AccountInfo<'info>,
+}
+
+/// Read-only solvency report.
+/// remaining_accounts: [vault_state_0, vault_0, vault_state_1, vault_1, ...], at most VaultReserve::MAX_REPORTED pairs
+pub fn report_reserves<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReportReserves<'info>>,
+) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty()
+            && ctx.remaining_accounts.len() % 2 == 0
+            && ctx.remaining_accounts.len() / 2 <= VaultReserve::MAX_REPORTED,
+        XBridgeErrorCode::InvalidAccountsLength
+    );
+
+    let mut reserves: Vec<VaultReserve> = Vec::with_capacity(ctx.remaining_accounts.len() / 2);
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let vault_state: Account<VaultState> = Account::try_from(&pair[0])?;
+        let vault: InterfaceAccount<TokenAccount> = InterfaceAccount::try_from(&pair[1])?;
+        require!(
+            vault.key() == vault_state.vault &&
+            vault.owner == ctx.accounts.xbridge_authority.key(),
+            XBridgeErrorCode::InvalidAccount
+        );
+
+        reserves.push(VaultReserve {
+            mint: vault_state.mint,
+            vault: vault_state.vault,
+            vault_balance: vault.amount,
+            outstanding: vault_state.outstanding,
+            total_claimed: vault_state.total_claimed,
+            total_refunded: vault_state.total_refunded,
+        });
+    }
+
+    set_return_data(&reserves.try_to_vec()?);
+    emit!(ReservesReported { reserves });
+    Ok(())
+}
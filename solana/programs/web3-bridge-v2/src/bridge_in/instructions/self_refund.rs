@@ -0,0 +1,191 @@
+use {
+    crate::{
+        bridge_in::{
+            BridgeMessage,
+            ToSwapMessageState,
+            VaultState,
+        },
+        common::XBridgeErrorCode,
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::
+            system_program
+        ,
+    },
+    anchor_spl::{
+        associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken, Create},
+        token_interface::{self, TokenInterface, TokenAccount, Mint, TransferChecked}
+    },
+};
+
+
+#[derive(Accounts)]
+pub struct SelfRefund<'info> {
+    #[account(mut)]
+    /// The end recipient.
+    /// This account must be BridgeMessage.to of the verified message.
+    pub recipient: Signer<'info>,
+
+    /// The authority PDA derived from xbridge_program
+    /// CHECK: This account is the authority derived from the xbridge_program.
+    #[account(
+        seeds = [b"xbridge_authority_pda"],
+        bump,
+    )]
+    pub xbridge_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::authority = xbridge_authority,
+        token::token_program = token_program,
+    )]
+    pub xbridge_source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", source_mint.key().as_ref()],
+        bump
+    )]
+    /// Vault state PDA of source_mint, settled when source_mint has a create_vault vault.
+    /// CHECK: seeds checked, empty while source_mint has no vault, loaded by VaultState::load_for_vault
+    pub vault_state: UncheckedAccount<'info>,
+
+    /// CHECK: The refund recipient, verified against BridgeMessage.refund_to (or BridgeMessage.to)
+    pub refund_owner: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: This is the refund_owner's from_token ATA, created idempotently and verified in self_refund
+    pub refund_token_account: AccountInfo<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub source_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub toswap_message_request: Box<Account<'info, ToSwapMessageState>>,
+
+    /// SPL Token Program or SPL Token 2022 Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" or "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+    /// CHECK: fixed
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Associated Token Program
+    /// "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+    /// CHECK: fixed
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    #[account(address = system_program::id())]
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SelfRefundArgs {
+    pub orderid: u128,
+}
+
+pub fn self_refund<'info>(
+    _ctx: Context<'_, '_, '_, 'info, SelfRefund<'info>>,
+    data: SelfRefundArgs,
+) -> Result<()> {
+    // toswap_message_request.is_used != true
+    let toswap_message_request = &mut _ctx.accounts.toswap_message_request;
+    require!(
+        !toswap_message_request.is_used,
+        XBridgeErrorCode::ToswapAlreadyUsed
+    );
+
+    // verify the self refund delay snapshotted at the first verify has passed, self refund is not paused
+    require!(
+        toswap_message_request.refundable_at > 0
+            && Clock::get()?.unix_timestamp >= toswap_message_request.refundable_at,
+        XBridgeErrorCode::SelfRefundDelayNotReached
+    );
+
+    // read toswap_message_request.data
+    let request_src_chain_message = BridgeMessage::try_from_slice(&toswap_message_request.data)?;
+    BridgeMessage::msg_oracle_data(&request_src_chain_message, data.orderid);
+    // verify recipient == to
+    require!(
+        Pubkey::from(request_src_chain_message.to) == _ctx.accounts.recipient.key(),
+        XBridgeErrorCode::Unauthorized
+    );
+    // verify refund_owner == refund_to (or to, when no refund_to is signed)
+    let refund_address = request_src_chain_message.refund_address();
+    require!(
+        refund_address == _ctx.accounts.refund_owner.key(),
+        XBridgeErrorCode::InvalidDexSwapArgsRefundAddress
+    );
+    // verify (refund_address & source_mint) == _ctx.accounts.refund_token_account, spl-token or spl-2022-token
+    require!(
+        get_associated_token_address_with_program_id(&refund_address, &_ctx.accounts.source_mint.key(), &_ctx.accounts.token_program.key()) == _ctx.accounts.refund_token_account.key(),
+        XBridgeErrorCode::InvalidDexSwapArgsRefundAddress
+    );
+    // verify from_token_address == _ctx.accounts.source_mint
+    let from_token_address = Pubkey::from(request_src_chain_message.from_token);
+    require!(
+        from_token_address == _ctx.accounts.source_mint.key(),
+        XBridgeErrorCode::InvalidDexSwapArgsFromTokenAddress
+    );
+    // refund exactly the attested from_amount, no gas refund fee is charged
+    let from_amount_bytes = &request_src_chain_message.from_amount[24..32];
+    let from_amount = u64::from_be_bytes(from_amount_bytes.try_into().expect("slice with incorrect length"));
+
+    let authority_seeds: &[&[u8]] = &[b"xbridge_authority_pda", &[_ctx.bumps.xbridge_authority]];
+    let signer_seeds = [
+            &authority_seeds[..]
+    ];
+
+    if from_amount > 0 {
+        let token_program = _ctx.accounts.token_program.to_account_info();
+        let source_mint = _ctx.accounts.source_mint.to_account_info();
+        associated_token::create_idempotent(
+            CpiContext::new(
+                _ctx.accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: _ctx.accounts.recipient.to_account_info(),
+                    associated_token: _ctx.accounts.refund_token_account.to_account_info(),
+                    authority: _ctx.accounts.refund_owner.to_account_info(),
+                    mint: source_mint.clone(),
+                    system_program: _ctx.accounts.system_program.to_account_info(),
+                    token_program: token_program.clone(),
+                },
+            )
+        )?;
+
+        let cpi_accounts = TransferChecked {
+            from: _ctx.accounts.xbridge_source_token_account.to_account_info(),
+            mint: source_mint,
+            to: _ctx.accounts.refund_token_account.to_account_info(),
+            authority: _ctx.accounts.xbridge_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, &signer_seeds);
+        token_interface::transfer_checked(cpi_ctx, from_amount, _ctx.accounts.source_mint.decimals)?;
+    }
+
+    // mints without a vault refund from the operator funded token account alone
+    if let Some(mut vault_state) = VaultState::load_for_vault(
+        &_ctx.accounts.vault_state,
+        &_ctx.accounts.xbridge_source_token_account.key(),
+    )? {
+        vault_state.record_refund(from_amount, from_amount)?;
+        // the payout is backed by owner and share holders' liquidity pro rata
+        _ctx.accounts.xbridge_source_token_account.reload()?;
+        vault_state.mark_to_vault(_ctx.accounts.xbridge_source_token_account.amount)?;
+        vault_state.store(&_ctx.accounts.vault_state)?;
+    }
+    toswap_message_request.is_used = true;
+
+    msg!(
+        "Self refund by recipient: {}, refund_to: {}, amount: {}",
+        _ctx.accounts.recipient.key(),
+        refund_address,
+        from_amount
+    );
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use {
+    crate::bridge_in::{ContractConfig, GasRefundConfig},
+    anchor_lang::prelude::*,
+};
+
+
+#[derive(Accounts)]
+pub struct SetGasRefundContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    // gasRefundConfig state account
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + GasRefundConfig::INIT_SPACE,
+        seeds = [b"gasrefund_config"],
+        bump
+    )]
+    pub gasrefund_config: Account<'info, GasRefundConfig>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct SetGasRefundParams {
+    pub new_gasrefund: Pubkey,
+}
+
+pub fn set_gasrefund(ctx: Context<SetGasRefundContext>, data: SetGasRefundParams) -> Result<()> {
+    let gasrefund_config = &mut ctx.accounts.gasrefund_config;
+
+    gasrefund_config.gasrefund = data.new_gasrefund;
+
+    msg!(
+        "Gasrefund address updated to: {} by owner: {}",
+        data.new_gasrefund,
+        ctx.accounts.owner.key()
+    );
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use {
+    crate::{
+        bridge_in::{ContractConfig, GasRefundFeeCap, GasRefundLedger},
+        common::{XBridgeErrorCode, BPS_DENOMINATOR},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+
+#[derive(Accounts)]
+pub struct SetGasRefundFeeCapContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // gasRefundLedger state account, one per source mint
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + GasRefundLedger::INIT_SPACE,
+        seeds = [b"gasrefund_ledger", mint.key().as_ref()],
+        bump
+    )]
+    pub gasrefund_ledger: Account<'info, GasRefundLedger>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct SetGasRefundFeeCapParams {
+    pub fee_cap: GasRefundFeeCap,
+}
+
+pub fn set_gasrefund_fee_cap(ctx: Context<SetGasRefundFeeCapContext>, data: SetGasRefundFeeCapParams) -> Result<()> {
+    if let GasRefundFeeCap::Bps(bps) = data.fee_cap {
+        require!(
+            bps as u64 <= BPS_DENOMINATOR,
+            XBridgeErrorCode::InvalidGasRefundFeeCap
+        );
+    }
+
+    let gasrefund_ledger = &mut ctx.accounts.gasrefund_ledger;
+    gasrefund_ledger.mint = ctx.accounts.mint.key();
+    gasrefund_ledger.fee_cap = data.fee_cap;
+
+    msg!(
+        "Gasrefund fee cap for mint: {} updated to: {:?} by owner: {}",
+        gasrefund_ledger.mint,
+        gasrefund_ledger.fee_cap,
+        ctx.accounts.owner.key()
+    );
+    Ok(())
+}
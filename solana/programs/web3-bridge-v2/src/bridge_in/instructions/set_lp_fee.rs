@@ -0,0 +1,51 @@
+use {
+    crate::{
+        bridge_in::{ContractConfig, VaultState},
+        common::{XBridgeErrorCode, BPS_DENOMINATOR},
+    },
+    anchor_lang::prelude::*,
+};
+
+
+#[derive(Accounts)]
+pub struct SetLpFeeContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.mint.as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct SetLpFeeParams {
+    pub lp_fee_bps: u16,
+}
+
+pub fn set_lp_fee(ctx: Context<SetLpFeeContext>, data: SetLpFeeParams) -> Result<()> {
+    require!(
+        data.lp_fee_bps as u64 <= BPS_DENOMINATOR,
+        XBridgeErrorCode::InvalidLpFee
+    );
+
+    let vault_state = &mut ctx.accounts.vault_state;
+    vault_state.lp_fee_bps = data.lp_fee_bps;
+
+    msg!(
+        "Lp fee for mint: {} updated to: {}bps by owner: {}",
+        vault_state.mint,
+        data.lp_fee_bps,
+        ctx.accounts.owner.key()
+    );
+    Ok(())
+}
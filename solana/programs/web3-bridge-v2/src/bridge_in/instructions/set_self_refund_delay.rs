@@ -0,0 +1,58 @@
+use {
+    crate::{
+        bridge_in::{ContractConfig, SelfRefundConfig},
+        common::{XBridgeErrorCode, SELF_REFUND_MAX_DELAY, SELF_REFUND_MIN_DELAY},
+    },
+    anchor_lang::prelude::*,
+};
+
+
+#[derive(Accounts)]
+pub struct SetSelfRefundDelayContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    // selfRefundConfig state account
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + SelfRefundConfig::INIT_SPACE,
+        seeds = [b"self_refund_config"],
+        bump
+    )]
+    pub self_refund_config: Account<'info, SelfRefundConfig>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct SetSelfRefundDelayParams {
+    pub delay: i64,
+}
+
+pub fn set_self_refund_delay(ctx: Context<SetSelfRefundDelayContext>, data: SetSelfRefundDelayParams) -> Result<()> {
+    require!(
+        data.delay >= SELF_REFUND_MIN_DELAY && data.delay <= SELF_REFUND_MAX_DELAY,
+        XBridgeErrorCode::InvalidSelfRefundDelay
+    );
+
+    let self_refund_config = &mut ctx.accounts.self_refund_config;
+    self_refund_config.delay = data.delay;
+
+    msg!(
+        "Self refund delay updated to: {}s by owner: {}",
+        data.delay,
+        ctx.accounts.owner.key()
+    );
+    Ok(())
+}
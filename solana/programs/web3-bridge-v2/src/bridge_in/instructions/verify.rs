@@ -3,11 +3,15 @@ use {
         bridge_in::{
             ContractConfig,
             BridgeMessage,
+            SelfRefundConfig,
             ToSwapMessageState,
+            VaultState,
         },
         common::{
+            init_or_realloc_pda,
             public_key_to_address,
             XBridgeErrorCode,
+            SELF_REFUND_MAX_DELAY,
             TEST_MPC_STR,
             TEST_MPC_STR_2
         },
@@ -31,19 +35,31 @@ pub struct Verify<'info> {
     pub payer: Signer<'info>,
 
     #[account(
-        init_if_needed,
-        payer = payer,
-        space = 8 + ToSwapMessageState::INIT_SPACE,
+        mut,
         seeds = [
             b"toswap_message",
             &BridgeMessage::from_message(&params.message)?.src_chain_id()[..],
             &BridgeMessage::from_message(&params.message)?.src_tx_hash()[..]
         ],
         bump
-        
     )]
-    /// CHECK: 
-    pub toswap_message_request: Box<Account<'info, ToSwapMessageState>>,
+    /// ToSwapMessageState, created on the first verify.
+    /// States created before to_token and refund_to were added are grown on re-verify.
+    /// CHECK: seeds checked, owner and size handled by init_or_realloc_pda
+    pub toswap_message_request: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vault_state",
+            &BridgeMessage::from_message(&params.message)?.from_token()[..]
+        ],
+        bump
+    )]
+    /// Per-mint vault state PDA of the message's from_token, tracks the outstanding amount.
+    /// Required for every mint with a create_vault vault.
+    /// CHECK: seeds checked, empty while from_token has no vault, loaded by VaultState::load
+    pub vault_state: UncheckedAccount<'info>,
 
     #[account(
         constraint = payer.key() == contract_config.mpc ||
@@ -56,6 +72,13 @@ pub struct Verify<'info> {
     /// CHECK:
     pub contract_config: Account<'info, ContractConfig>,
 
+    #[account(
+        seeds = [b"self_refund_config"],
+        bump
+    )]
+    /// Self refund delay snapshotted into the message state, SELF_REFUND_MAX_DELAY when not set
+    pub self_refund_config: Option<Account<'info, SelfRefundConfig>>,
+
     /// System Program.
     /// "11111111111111111111111111111111"
     /// CHECK: fixed
@@ -121,46 +144,95 @@ pub fn verify<'info>(
     );
 
     // use src_chain_id and src_tx_hash as seeds to create a PDA account for each transaction from the source chain.
-    let oracle_src_chain_message = BridgeMessage::try_from_slice(&data.message)?;
+    let oracle_src_chain_message = BridgeMessage::from_message(&data.message)?;
     BridgeMessage::msg_oracle_data(&oracle_src_chain_message, data.orderid);
+    let fixed_data = BridgeMessage::to_fixed_bytes(&data.message)?;
 
-    if _ctx.accounts.toswap_message_request.data.iter().all(|&x| x == 0) {
-        // if toswap_message_request is created for the first time
-        // write message to it
-        let mut fixed_data: [u8; 160] = [0; 160];
-        let message_len = data.message.len();
-        if message_len > 160 {
-            return Err(XBridgeErrorCode::DataTooLong.into());
+    let toswap_message_info = _ctx.accounts.toswap_message_request.to_account_info();
+    let bump = [_ctx.bumps.toswap_message_request];
+    let toswap_message_seeds: &[&[u8]] = &[
+        b"toswap_message",
+        oracle_src_chain_message.src_chain_id(),
+        oracle_src_chain_message.src_tx_hash(),
+        &bump,
+    ];
+    let is_new = init_or_realloc_pda(
+        &_ctx.accounts.payer.to_account_info(),
+        &toswap_message_info,
+        &_ctx.accounts.system_program.to_account_info(),
+        8 + ToSwapMessageState::INIT_SPACE,
+        _ctx.program_id,
+        toswap_message_seeds,
+    )?;
+    let mut vault_state = VaultState::load(&_ctx.accounts.vault_state)?;
+    let mut toswap_message_state_data = if is_new {
+        ToSwapMessageState {
+            is_used: false,
+            authority: Pubkey::default(),
+            authority_program: Pubkey::default(),
+            data: [0; BridgeMessage::LEN],
+            verified_at: 0,
+            refundable_at: 0,
         }
-        fixed_data[..message_len].copy_from_slice(&data.message[..message_len]);
+    } else {
+        // a reallocated legacy state reads its 160 byte message padded with zero to_token and refund_to
+        ToSwapMessageState::try_deserialize(&mut &toswap_message_info.try_borrow_data()?[..])?
+    };
 
-        let toswap_message_state_data = &mut _ctx.accounts.toswap_message_request;
+    // the self refund clock starts at the first verify, a re-verify or a later delay change does not move it
+    // states grown from an older layout get their snapshot on this verify
+    if toswap_message_state_data.refundable_at == 0 {
+        let self_refund_delay = _ctx.accounts.self_refund_config
+            .as_ref()
+            .map_or(SELF_REFUND_MAX_DELAY, |config| config.delay);
+        if toswap_message_state_data.verified_at == 0 {
+            toswap_message_state_data.verified_at = Clock::get()?.unix_timestamp;
+        }
+        toswap_message_state_data.refundable_at = toswap_message_state_data.verified_at
+            .checked_add(self_refund_delay)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+    }
+
+    if toswap_message_state_data.data.iter().all(|&x| x == 0) {
+        // if toswap_message_request is created for the first time
+        // write message to it
         toswap_message_state_data.is_used = false;
         toswap_message_state_data.authority = _ctx.accounts.payer.key();
         toswap_message_state_data.authority_program = *_ctx.program_id;
         toswap_message_state_data.data = fixed_data;
+        if let Some(vault_state) = &mut vault_state {
+            vault_state.record_verify(0, oracle_src_chain_message.from_amount())?;
+        }
         msg!("toswap_message_state_data: {:?}", toswap_message_state_data);
     } else {
         // if the account has been created
         // when the toswap_message_request.is_used != true (no dex or refund has been performed)
         // the signature verification has passed
         // allowed to rewrite information to the toswap_message_request account
-        let toswap_message_state_data = &mut _ctx.accounts.toswap_message_request;
         require!(
             !toswap_message_state_data.is_used,
             XBridgeErrorCode::ToswapAlreadyUsed
         );
 
-        let mut fixed_data: [u8; 160] = [0; 160];
-        let message_len = data.message.len();
-        if message_len > 160 {
-            return Err(XBridgeErrorCode::DataTooLong.into());
-        }
-        fixed_data[..message_len].copy_from_slice(&data.message[..message_len]);
+        // the rewritten message must stay on the same vault, replace its outstanding amount
+        let previous_src_chain_message = BridgeMessage::from_message(&toswap_message_state_data.data)?;
+        require!(
+            previous_src_chain_message.from_token == oracle_src_chain_message.from_token,
+            XBridgeErrorCode::OracleTokenAddressErr
+        );
+
         toswap_message_state_data.data = fixed_data;
+        if let Some(vault_state) = &mut vault_state {
+            vault_state.record_verify(previous_src_chain_message.from_amount(), oracle_src_chain_message.from_amount())?;
+        }
 
         msg!("Updated toswap_message_state_data: {:?}", toswap_message_state_data);
     }
 
+    toswap_message_state_data.try_serialize(&mut &mut toswap_message_info.try_borrow_mut_data()?[..])?;
+    if let Some(vault_state) = &vault_state {
+        vault_state.store(&_ctx.accounts.vault_state)?;
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
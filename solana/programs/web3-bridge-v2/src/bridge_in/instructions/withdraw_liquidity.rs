@@ -0,0 +1,101 @@
+use {
+    crate::{
+        bridge_in::{ContractConfig, LiquidityWithdrawn, VaultState},
+        common::XBridgeErrorCode,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+
+#[derive(Accounts)]
+pub struct WithdrawLiquidityContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    /// The authority PDA derived from xbridge_program
+    /// CHECK: This account is the authority derived from the xbridge_program.
+    #[account(
+        seeds = [b"xbridge_authority_pda"],
+        bump,
+    )]
+    pub xbridge_authority: AccountInfo<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = mint @ XBridgeErrorCode::InvalidAccount,
+        has_one = vault @ XBridgeErrorCode::InvalidAccount,
+        has_one = token_program @ XBridgeErrorCode::InvalidAccount,
+        seeds = [b"vault_state", mint.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// SPL Token Program or SPL Token 2022 Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA" or "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+    /// CHECK: fixed
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct WithdrawLiquidityParams {
+    pub amount: u64,
+}
+
+pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidityContext>, data: WithdrawLiquidityParams) -> Result<()> {
+    require!(data.amount > 0, XBridgeErrorCode::InvalidLiquidityAmount);
+    // payouts and replenishments since the last mark move both sides pro rata
+    let vault_amount = ctx.accounts.vault.amount;
+    ctx.accounts.vault_state.mark_to_vault(vault_amount)?;
+    // share holders' liquidity is only withdrawable through lp_withdraw
+    require!(
+        data.amount <= ctx.accounts.vault_state.liquidity,
+        XBridgeErrorCode::InsufficientVaultLiquidity
+    );
+
+    let authority_seeds: &[&[u8]] = &[b"xbridge_authority_pda", &[ctx.bumps.xbridge_authority]];
+    let signer_seeds = [
+            &authority_seeds[..]
+    ];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.destination_token_account.to_account_info(),
+        authority: ctx.accounts.xbridge_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, &signer_seeds);
+    token_interface::transfer_checked(cpi_ctx, data.amount, ctx.accounts.mint.decimals)?;
+
+    let vault_state = &mut ctx.accounts.vault_state;
+    vault_state.liquidity -= data.amount;
+    vault_state.total_withdrawn = vault_state.total_withdrawn
+        .checked_add(data.amount)
+        .ok_or(XBridgeErrorCode::CalculationError)?;
+
+    emit!(LiquidityWithdrawn {
+        mint: vault_state.mint,
+        vault: vault_state.vault,
+        to: ctx.accounts.destination_token_account.key(),
+        amount: data.amount,
+        liquidity: vault_state.liquidity,
+    });
+    Ok(())
+}
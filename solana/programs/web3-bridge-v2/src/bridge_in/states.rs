@@ -1,4 +1,10 @@
-use anchor_lang::prelude::*;
+use {
+    crate::{
+        bridge_in::BridgeMessage,
+        common::{XBridgeErrorCode, BPS_DENOMINATOR, LP_VIRTUAL_LIQUIDITY},
+    },
+    anchor_lang::prelude::*,
+};
 
 #[account]
 #[derive(InitSpace, Debug)]
@@ -6,7 +12,14 @@ pub struct ToSwapMessageState {
     pub is_used: bool, // 1
     pub authority: Pubkey, // 32                
     pub authority_program: Pubkey, // 32 
-    pub data: [u8; 160],    // 5 * 32 = 160
+    pub data: [u8; BridgeMessage::LEN],    // 7 * 32 = 224
+    pub verified_at: i64, // 8, unix timestamp of the first verify
+    pub refundable_at: i64, // 8, verified_at plus the self refund delay snapshotted at the first verify
+}
+
+impl ToSwapMessageState {
+    // states created before to_token, refund_to, verified_at and refundable_at were added
+    pub const LEGACY_SPACE: usize = 1 + 32 + 32 + BridgeMessage::LEGACY_LEN;
 }
 
 #[account]
@@ -17,4 +30,396 @@ pub struct ContractConfig {
     pub paused: bool,           // Boolean value indicating whether the program is paused
     pub oracle: [u8; 20],       // The oracle address
     pub mpc: Pubkey,            // The MPC (Multi-Party Computation) address
-}
\ No newline at end of file
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SelfRefundConfig {
+    pub delay: i64,             // Seconds after the first verify before BridgeMessage.to may refund itself
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct GasRefundConfig {
+    pub gasrefund: Pubkey,      // The only account allowed to receive create_pda_fee
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Debug, Clone, Copy, PartialEq)]
+pub enum GasRefundFeeCap {
+    Amount(u64),                // Absolute fee ceiling, in the smallest unit of the mint
+    Bps(u16),                   // Fee ceiling in basis points of the attested from_amount
+    Uncapped,                   // No ceiling, the ledger was created by a claim or refund before set_gasrefund_fee_cap
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct GasRefundLedger {
+    pub mint: Pubkey,               // The source mint the fee is collected in
+    pub fee_cap: GasRefundFeeCap,   // Maximum create_pda_fee accepted per claim or refund
+    pub total_collected: u64,       // Cumulative create_pda_fee paid to gasrefund, excluding the share holders' slice
+    pub collected_count: u64,       // Number of claims and refunds that paid a fee
+}
+
+impl GasRefundLedger {
+    // a ledger created by init_if_needed in claim or refund has no mint and no cap yet
+    pub fn init_if_new(&mut self, mint: Pubkey) {
+        if self.mint == Pubkey::default() {
+            self.mint = mint;
+            self.fee_cap = GasRefundFeeCap::Uncapped;
+        }
+    }
+
+    pub fn max_fee(&self, from_amount: u64) -> Result<u64> {
+        match self.fee_cap {
+            GasRefundFeeCap::Amount(amount) => Ok(amount),
+            GasRefundFeeCap::Bps(bps) => Ok(from_amount
+                .checked_mul(bps as u64)
+                .ok_or(XBridgeErrorCode::CalculationError)?
+                .checked_div(BPS_DENOMINATOR)
+                .ok_or(XBridgeErrorCode::CalculationError)?),
+            GasRefundFeeCap::Uncapped => Ok(u64::MAX),
+        }
+    }
+
+    pub fn check_fee(&self, fee: u64, from_amount: u64) -> Result<()> {
+        require!(
+            fee <= self.max_fee(from_amount)?,
+            XBridgeErrorCode::GasRefundFeeExceedsCap
+        );
+        Ok(())
+    }
+
+    // records the fee paid out to gasrefund, net of the share holders' slice
+    pub fn record_fee(&mut self, gasrefund_fee: u64) -> Result<()> {
+        if gasrefund_fee > 0 {
+            self.total_collected = self.total_collected
+                .checked_add(gasrefund_fee)
+                .ok_or(XBridgeErrorCode::CalculationError)?;
+            self.collected_count = self.collected_count
+                .checked_add(1)
+                .ok_or(XBridgeErrorCode::CalculationError)?;
+        }
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VaultState {
+    pub mint: Pubkey,               // The mint held by the vault
+    pub vault: Pubkey,              // The xbridge_authority_pda ATA of the mint
+    pub token_program: Pubkey,      // SPL Token or SPL Token 2022 program owning the mint
+    pub liquidity: u64,             // Owner liquidity currently accounted in the vault, backs payouts pro rata with lp_liquidity
+    pub total_deposited: u64,       // Cumulative deposit_liquidity amount
+    pub total_withdrawn: u64,       // Cumulative withdraw_liquidity amount
+    pub share_mint: Pubkey,         // LP share mint, minted and burned by xbridge_authority_pda
+    pub lp_liquidity: u64,          // Liquidity owned by share holders, including accrued fees
+    pub lp_fee_bps: u16,            // Slice of create_pda_fee accruing to share holders
+    pub outstanding: u64,           // Sum of verified but not yet claimed or refunded from_amount
+    pub total_claimed: u64,         // Cumulative amount delivered by claim and claim_to_sol
+    pub total_refunded: u64,        // Cumulative amount delivered by refund and self_refund
+}
+
+impl VaultState {
+    // shares minted for `amount` at the current share price, LP_VIRTUAL_LIQUIDITY on both sides
+    // keeps a tiny first deposit from setting the price
+    pub fn shares_for_deposit(&self, amount: u64, share_supply: u64) -> Result<u64> {
+        let shares = (amount as u128)
+            .checked_mul(share_supply as u128 + LP_VIRTUAL_LIQUIDITY as u128)
+            .ok_or(XBridgeErrorCode::CalculationError)?
+            .checked_div(self.lp_liquidity as u128 + LP_VIRTUAL_LIQUIDITY as u128)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        u64::try_from(shares).map_err(|_| XBridgeErrorCode::CalculationError.into())
+    }
+
+    // liquidity redeemed by burning `shares` at the current share price
+    pub fn amount_for_shares(&self, shares: u64, share_supply: u64) -> Result<u64> {
+        require!(shares <= share_supply, XBridgeErrorCode::CalculationError);
+        let amount = (shares as u128)
+            .checked_mul(self.lp_liquidity as u128 + LP_VIRTUAL_LIQUIDITY as u128)
+            .ok_or(XBridgeErrorCode::CalculationError)?
+            .checked_div(share_supply as u128 + LP_VIRTUAL_LIQUIDITY as u128)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        Ok(u64::try_from(amount)
+            .map_err(|_| XBridgeErrorCode::CalculationError)?
+            .min(self.lp_liquidity))
+    }
+
+    // marks owner and share holders' liquidity to the vault balance, pro rata to their current split,
+    // payouts are backed by both and replenishments of the vault accrue to both
+    pub fn mark_to_vault(&mut self, vault_amount: u64) -> Result<()> {
+        let tracked = (self.liquidity as u128)
+            .checked_add(self.lp_liquidity as u128)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        if tracked == 0 {
+            self.liquidity = vault_amount;
+            return Ok(());
+        }
+        let lp_liquidity = (self.lp_liquidity as u128)
+            .checked_mul(vault_amount as u128)
+            .ok_or(XBridgeErrorCode::CalculationError)?
+            .checked_div(tracked)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        self.lp_liquidity = u64::try_from(lp_liquidity).map_err(|_| XBridgeErrorCode::CalculationError)?;
+        self.liquidity = vault_amount - self.lp_liquidity;
+        Ok(())
+    }
+
+    // vault state at the canonical vault_state PDA of a mint, None while the mint has no create_vault vault
+    pub fn load(vault_state_info: &AccountInfo) -> Result<Option<VaultState>> {
+        if vault_state_info.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*vault_state_info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        Ok(Some(VaultState::try_deserialize(&mut &vault_state_info.try_borrow_data()?[..])?))
+    }
+
+    // as load, for a payout from `vault`
+    pub fn load_for_vault(vault_state_info: &AccountInfo, vault: &Pubkey) -> Result<Option<VaultState>> {
+        let vault_state = Self::load(vault_state_info)?;
+        if let Some(vault_state) = &vault_state {
+            require_keys_eq!(vault_state.vault, *vault, XBridgeErrorCode::InvalidAccount);
+        }
+        Ok(vault_state)
+    }
+
+    pub fn store(&self, vault_state_info: &AccountInfo) -> Result<()> {
+        self.try_serialize(&mut &mut vault_state_info.try_borrow_mut_data()?[..])
+    }
+
+    // a first verify has no previous amount, a re-verify replaces the previous from_amount
+    pub fn record_verify(&mut self, previous_amount: u64, from_amount: u64) -> Result<()> {
+        self.outstanding = self.outstanding
+            .saturating_sub(previous_amount)
+            .checked_add(from_amount)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        Ok(())
+    }
+
+    pub fn record_claim(&mut self, from_amount: u64, amount: u64) -> Result<()> {
+        self.outstanding = self.outstanding.saturating_sub(from_amount);
+        self.total_claimed = self.total_claimed
+            .checked_add(amount)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        Ok(())
+    }
+
+    pub fn record_refund(&mut self, from_amount: u64, amount: u64) -> Result<()> {
+        self.outstanding = self.outstanding.saturating_sub(from_amount);
+        self.total_refunded = self.total_refunded
+            .checked_add(amount)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        Ok(())
+    }
+
+    // keeps the share holders' slice of create_pda_fee in the vault, returns that slice
+    pub fn accrue_lp_fee(&mut self, create_pda_fee: u64) -> Result<u64> {
+        if self.lp_liquidity == 0 {
+            return Ok(0);
+        }
+        let lp_fee = create_pda_fee
+            .checked_mul(self.lp_fee_bps as u64)
+            .ok_or(XBridgeErrorCode::CalculationError)?
+            .checked_div(BPS_DENOMINATOR)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        self.lp_liquidity = self.lp_liquidity
+            .checked_add(lp_fee)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        Ok(lp_fee)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WormholeRedeemState {
+    pub vaa_hash: [u8; 32],         // Hash of the redeemed VAA, also the PDA seed
+    pub redeemed_at: i64,           // Unix timestamp of the redeem
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::{solana_program::system_program, Discriminator};
+
+    fn vault_state(liquidity: u64, lp_liquidity: u64, lp_fee_bps: u16) -> VaultState {
+        VaultState {
+            mint: Pubkey::default(),
+            vault: Pubkey::default(),
+            token_program: Pubkey::default(),
+            liquidity,
+            total_deposited: 0,
+            total_withdrawn: 0,
+            share_mint: Pubkey::default(),
+            lp_liquidity,
+            lp_fee_bps,
+            outstanding: 0,
+            total_claimed: 0,
+            total_refunded: 0,
+        }
+    }
+
+    #[test]
+    fn shares_for_deposit() {
+        // an empty vault mints 1:1
+        assert_eq!(vault_state(0, 0, 0).shares_for_deposit(1_000, 0).unwrap(), 1_000);
+        // at a price of 2 liquidity per share, half the shares
+        assert_eq!(
+            vault_state(0, 3 * LP_VIRTUAL_LIQUIDITY, 0).shares_for_deposit(2_000, LP_VIRTUAL_LIQUIDITY).unwrap(),
+            1_000
+        );
+        // share holders' liquidity lost to zero with shares outstanding no longer mints 1:1
+        assert_eq!(vault_state(0, 0, 0).shares_for_deposit(1_000, LP_VIRTUAL_LIQUIDITY).unwrap(), 2_000);
+        // a 1 unit first deposit followed by a large fee accrual does not round the next deposit to zero
+        let mut state = vault_state(0, 1, 10_000);
+        state.accrue_lp_fee(1_000_000_000).unwrap();
+        assert!(state.shares_for_deposit(1_000_000, 1).unwrap() > 0);
+    }
+
+    #[test]
+    fn amount_for_shares() {
+        let state = vault_state(0, 3_000, 0);
+        assert_eq!(state.amount_for_shares(0, 3_000).unwrap(), 0);
+        assert_eq!(state.amount_for_shares(3_000, 3_000).unwrap(), 3_000);
+        assert!(state.amount_for_shares(3_001, 3_000).is_err());
+        // never more than the share holders' liquidity
+        let state = vault_state(0, 1_000, 0);
+        assert_eq!(state.amount_for_shares(2_000, 2_000).unwrap(), 1_000);
+        // a deposit followed by a withdraw of the minted shares returns at most the deposit
+        let state = vault_state(0, 5_000_000, 0);
+        let shares = state.shares_for_deposit(1_234_567, 4_000_000).unwrap();
+        let after = vault_state(0, 5_000_000 + 1_234_567, 0);
+        assert!(after.amount_for_shares(shares, 4_000_000 + shares).unwrap() <= 1_234_567);
+    }
+
+    #[test]
+    fn accrue_lp_fee() {
+        // no share holders, the whole fee goes to gasrefund
+        let mut state = vault_state(0, 0, 5_000);
+        assert_eq!(state.accrue_lp_fee(1_000).unwrap(), 0);
+        assert_eq!(state.lp_liquidity, 0);
+
+        let mut state = vault_state(0, 10_000, 2_500);
+        assert_eq!(state.accrue_lp_fee(1_000).unwrap(), 250);
+        assert_eq!(state.lp_liquidity, 10_250);
+        assert_eq!(state.accrue_lp_fee(0).unwrap(), 0);
+    }
+
+    #[test]
+    fn record_verify_claim_refund() {
+        let mut state = vault_state(0, 0, 0);
+        state.record_verify(0, 1_000).unwrap();
+        state.record_verify(0, 500).unwrap();
+        assert_eq!(state.outstanding, 1_500);
+        // a re-verify replaces the previous amount
+        state.record_verify(500, 700).unwrap();
+        assert_eq!(state.outstanding, 1_700);
+
+        state.record_claim(1_000, 990).unwrap();
+        assert_eq!(state.outstanding, 700);
+        assert_eq!(state.total_claimed, 990);
+
+        state.record_refund(700, 700).unwrap();
+        assert_eq!(state.outstanding, 0);
+        assert_eq!(state.total_refunded, 700);
+
+        // outstanding never underflows
+        state.record_refund(1, 1).unwrap();
+        assert_eq!(state.outstanding, 0);
+    }
+
+    #[test]
+    fn gasrefund_ledger() {
+        let mut ledger = GasRefundLedger {
+            mint: Pubkey::default(),
+            fee_cap: GasRefundFeeCap::Amount(0),
+            total_collected: 0,
+            collected_count: 0,
+        };
+        // a ledger created by a claim is uncapped until set_gasrefund_fee_cap
+        let mint = Pubkey::new_unique();
+        ledger.init_if_new(mint);
+        assert_eq!(ledger.mint, mint);
+        ledger.check_fee(u64::MAX, 1).unwrap();
+
+        // a configured cap is kept by later claims
+        ledger.fee_cap = GasRefundFeeCap::Bps(100);
+        ledger.init_if_new(Pubkey::new_unique());
+        assert_eq!(ledger.mint, mint);
+        ledger.check_fee(10, 1_000).unwrap();
+        assert!(ledger.check_fee(11, 1_000).is_err());
+
+        ledger.record_fee(10).unwrap();
+        ledger.record_fee(0).unwrap();
+        assert_eq!((ledger.total_collected, ledger.collected_count), (10, 1));
+    }
+
+    #[test]
+    fn legacy_message_state() {
+        // a grown legacy state reads its message padded with a zero to_token and refund_to
+        let mut data = ToSwapMessageState::DISCRIMINATOR.to_vec();
+        data.push(0);
+        data.extend_from_slice(&[1u8; 64]);
+        data.extend_from_slice(&[2u8; BridgeMessage::LEGACY_LEN]);
+        assert_eq!(data.len(), 8 + ToSwapMessageState::LEGACY_SPACE);
+        data.resize(8 + ToSwapMessageState::INIT_SPACE, 0);
+        let state = ToSwapMessageState::try_deserialize(&mut &data[..]).unwrap();
+        assert!(!state.is_used);
+        assert_eq!(state.data[..BridgeMessage::LEGACY_LEN], [2u8; BridgeMessage::LEGACY_LEN]);
+        let message = BridgeMessage::try_from_slice(&state.data).unwrap();
+        assert_eq!(message.to_token, [0u8; 32]);
+        message.check_to_token(&Pubkey::new_unique()).unwrap();
+        assert_eq!(state.refundable_at, 0);
+    }
+
+    #[test]
+    fn load_vault_state() {
+        let key = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let mut lamports = 0;
+        // the PDA of a mint without a create_vault vault, e.g. on a self refund
+        let mut empty: [u8; 0] = [];
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut empty, &system_program::ID, false, 0);
+        assert!(VaultState::load_for_vault(&info, &vault).unwrap().is_none());
+
+        let mut state = vault_state(1_000, 500, 0);
+        state.vault = vault;
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        let owner = crate::ID;
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        let mut loaded = VaultState::load_for_vault(&info, &vault).unwrap().unwrap();
+        assert!(VaultState::load_for_vault(&info, &Pubkey::new_unique()).is_err());
+
+        // a self refund of 300 settles pro rata and is written back
+        loaded.record_refund(300, 300).unwrap();
+        loaded.mark_to_vault(1_200).unwrap();
+        loaded.store(&info).unwrap();
+        let stored = VaultState::load_for_vault(&info, &vault).unwrap().unwrap();
+        assert_eq!((stored.liquidity, stored.lp_liquidity, stored.total_refunded), (800, 400, 300));
+    }
+
+    #[test]
+    fn mark_to_vault() {
+        // a payout of 300 from 1_000 owner and 500 share holders' liquidity is backed 2:1
+        let mut state = vault_state(1_000, 500, 0);
+        state.mark_to_vault(1_200).unwrap();
+        assert_eq!((state.liquidity, state.lp_liquidity), (800, 400));
+        // a replenishment accrues 2:1 as well
+        state.mark_to_vault(1_500).unwrap();
+        assert_eq!((state.liquidity, state.lp_liquidity), (1_000, 500));
+        // share holders' liquidity alone funds a payout
+        let mut state = vault_state(0, 500, 0);
+        state.mark_to_vault(200).unwrap();
+        assert_eq!((state.liquidity, state.lp_liquidity), (0, 200));
+        // the owner takes the rounding, the split never exceeds the vault
+        let mut state = vault_state(1, 2, 0);
+        state.mark_to_vault(2).unwrap();
+        assert_eq!((state.liquidity, state.lp_liquidity), (1, 1));
+        // an untracked vault belongs to the owner
+        let mut state = vault_state(0, 0, 0);
+        state.mark_to_vault(700).unwrap();
+        assert_eq!((state.liquidity, state.lp_liquidity), (700, 0));
+        state.mark_to_vault(0).unwrap();
+        assert_eq!((state.liquidity, state.lp_liquidity), (0, 0));
+    }
+}
@@ -1,5 +1,6 @@
 use {
-    anchor_lang::prelude::*,
+    crate::common::XBridgeErrorCode,
+    anchor_lang::{prelude::*, solana_program::program::MAX_RETURN_DATA},
     serde::{Deserialize, Serialize},
 };
 
@@ -10,10 +11,28 @@ pub struct BridgeMessage {
     pub to: [u8; 32],
     pub from_token: [u8; 32],
     pub from_amount: [u8; 32],      
+    pub to_token: [u8; 32],         // destination mint the recipient must receive
+    pub refund_to: [u8; 32],        // refund recipient, all zero means refund to `to`
 }
 impl BridgeMessage {
+    pub const LEN: usize = 7 * 32;
+    // messages signed before to_token and refund_to were added
+    pub const LEGACY_LEN: usize = 5 * 32;
+
+    /// Parses a signed message, a legacy message reads as all zero to_token and refund_to.
     pub fn from_message(message: &[u8]) -> Result<Self> {
-        BridgeMessage::try_from_slice(message).map_err(|e| e.into())
+        Self::try_from_slice(&Self::to_fixed_bytes(message)?).map_err(|e| e.into())
+    }
+
+    /// Pads a legacy message to LEN, as stored in ToSwapMessageState.data.
+    pub fn to_fixed_bytes(message: &[u8]) -> Result<[u8; Self::LEN]> {
+        require!(
+            message.len() == Self::LEN || message.len() == Self::LEGACY_LEN,
+            XBridgeErrorCode::DeserializationError
+        );
+        let mut fixed = [0u8; Self::LEN];
+        fixed[..message.len()].copy_from_slice(message);
+        Ok(fixed)
     }
 
     pub fn src_chain_id(&self) -> &[u8; 32] {
@@ -24,6 +43,32 @@ impl BridgeMessage {
         &self.src_tx_hash
     }
 
+    pub fn from_token(&self) -> &[u8; 32] {
+        &self.from_token
+    }
+
+    pub fn from_amount(&self) -> u64 {
+        u64::from_be_bytes(self.from_amount[24..32].try_into().expect("slice with incorrect length"))
+    }
+
+    /// Checks the claimed destination mint against to_token.
+    /// Legacy messages carry no to_token and leave the destination mint unbound.
+    pub fn check_to_token(&self, destination_mint: &Pubkey) -> Result<()> {
+        require!(
+            self.to_token == [0u8; 32] || Pubkey::from(self.to_token) == *destination_mint,
+            XBridgeErrorCode::DestinationMintMismatch
+        );
+        Ok(())
+    }
+
+    pub fn refund_address(&self) -> Pubkey {
+        if self.refund_to == [0u8; 32] {
+            Pubkey::from(self.to)
+        } else {
+            Pubkey::from(self.refund_to)
+        }
+    }
+
     pub fn msg_oracle_data(
         parsed_data: &BridgeMessage,
         orderid: u128,
@@ -33,6 +78,8 @@ impl BridgeMessage {
         let to_base58 = bs58::encode(parsed_data.to).into_string();
         let from_token_base58 = bs58::encode(parsed_data.from_token).into_string();
         let from_amount = u64::from_be_bytes(parsed_data.from_amount[24..32].try_into().expect("slice with incorrect length"));
+        let to_token_base58 = bs58::encode(parsed_data.to_token).into_string();
+        let refund_to_base58 = parsed_data.refund_address().to_string();
     
         let oracle_data_log: BridgeMessageLog = BridgeMessageLog {
             src_chain_id: src_chain_id,
@@ -40,6 +87,8 @@ impl BridgeMessage {
             to: to_base58,
             from_token: from_token_base58,
             from_amount: from_amount,
+            to_token: to_token_base58,
+            refund_to: refund_to_base58,
             orderid: orderid
         };
         msg!("Parsed oracle data log:{}", serde_json::to_string(&oracle_data_log).unwrap())
@@ -53,5 +102,363 @@ struct BridgeMessageLog {
     pub to: String,               
     pub from_token: String,
     pub from_amount: u64,  
+    pub to_token: String,
+    pub refund_to: String,
     pub orderid: u128,    
-}
\ No newline at end of file
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct VaultReserve {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub vault_balance: u64,         // Tokens currently held by the vault
+    pub outstanding: u64,           // Verified but not yet claimed or refunded
+    pub total_claimed: u64,
+    pub total_refunded: u64,
+}
+
+impl VaultReserve {
+    pub const LEN: usize = 32 + 32 + 4 * 8;
+    // the most reserves a report fits in return data, behind the 4 byte vec length
+    pub const MAX_REPORTED: usize = (MAX_RETURN_DATA - 4) / Self::LEN;
+}
+
+/// CCTP v2 message carrying a BurnMessageV2, as attested by Circle.
+/// All integers are big-endian, uint256 amounts are read from their low 8 bytes.
+#[derive(Debug, Clone)]
+pub struct CctpBurnMessage {
+    pub source_domain: u32,
+    pub destination_domain: u32,
+    pub nonce: [u8; 32],
+    pub destination_caller: Pubkey,
+    pub burn_token: [u8; 32],
+    pub mint_recipient: Pubkey,
+    pub amount: u64,
+    pub fee_executed: u64,
+    pub hook_data: Vec<u8>,
+}
+
+impl CctpBurnMessage {
+    // MessageV2 header: version, source_domain, destination_domain, nonce, sender, recipient,
+    // destination_caller, min_finality_threshold, finality_threshold_executed
+    const HEADER_LEN: usize = 148;
+    // BurnMessageV2: version, burn_token, mint_recipient, amount, message_sender, max_fee,
+    // fee_executed, expiration_block, followed by hook_data
+    const BURN_LEN: usize = 228;
+
+    pub fn from_message(message: &[u8]) -> Result<Self> {
+        require!(
+            message.len() >= Self::HEADER_LEN + Self::BURN_LEN,
+            XBridgeErrorCode::InvalidCctpMessage
+        );
+        let body = &message[Self::HEADER_LEN..];
+        // amounts above u64 cannot be minted on Solana
+        require!(
+            body[68..92].iter().all(|b| *b == 0) && body[164..188].iter().all(|b| *b == 0),
+            XBridgeErrorCode::InvalidCctpMessage
+        );
+        Ok(CctpBurnMessage {
+            source_domain: u32::from_be_bytes(message[4..8].try_into().unwrap()),
+            destination_domain: u32::from_be_bytes(message[8..12].try_into().unwrap()),
+            nonce: message[12..44].try_into().unwrap(),
+            destination_caller: Pubkey::try_from(&message[108..140]).unwrap(),
+            burn_token: body[4..36].try_into().unwrap(),
+            mint_recipient: Pubkey::try_from(&body[36..68]).unwrap(),
+            amount: u64::from_be_bytes(body[92..100].try_into().unwrap()),
+            fee_executed: u64::from_be_bytes(body[188..196].try_into().unwrap()),
+            hook_data: body[Self::BURN_LEN..].to_vec(),
+        })
+    }
+
+    pub fn received_amount(&self) -> Result<u64> {
+        self.amount
+            .checked_sub(self.fee_executed)
+            .ok_or(XBridgeErrorCode::InvalidCctpMessage.into())
+    }
+}
+
+/// Payload of a trustless inbound transfer (CCTP hook data, Wormhole transfer payload):
+/// the Solana recipient, the token it receives, and the minimum amount of that token,
+/// signed by the user on the source chain.
+#[derive(Debug, Clone)]
+pub struct SwapPayload {
+    pub to: Pubkey,
+    pub to_token: Pubkey,
+    pub min_return: u64,
+}
+
+impl SwapPayload {
+    pub const LEN: usize = 32 + 32 + 8;
+
+    pub fn from_payload(payload: &[u8]) -> Result<Self> {
+        require!(payload.len() == Self::LEN, XBridgeErrorCode::InvalidSwapPayload);
+        Ok(SwapPayload {
+            to: Pubkey::try_from(&payload[0..32]).unwrap(),
+            to_token: Pubkey::try_from(&payload[32..64]).unwrap(),
+            min_return: u64::from_be_bytes(payload[64..72].try_into().unwrap()),
+        })
+    }
+}
+
+/// PostedVAA account of the Wormhole core bridge, without the b"vaa" prefix.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct PostedVaa {
+    pub vaa_version: u8,
+    pub consistency_level: u8,
+    pub vaa_time: u32,
+    pub vaa_signature_account: Pubkey,
+    pub submission_time: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+impl PostedVaa {
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        require!(data.len() > 3 && &data[..3] == b"vaa", XBridgeErrorCode::InvalidWormholeVaa);
+        PostedVaa::deserialize(&mut &data[3..]).map_err(|_| XBridgeErrorCode::InvalidWormholeVaa.into())
+    }
+}
+
+/// Token bridge TransferWithPayload (payload id 3), integers are big-endian.
+#[derive(Debug, Clone)]
+pub struct WormholeTransferWithPayload {
+    pub amount: u64,
+    pub token_address: [u8; 32],
+    pub token_chain: u16,
+    pub to: Pubkey,
+    pub to_chain: u16,
+    pub payload: Vec<u8>,
+}
+
+impl WormholeTransferWithPayload {
+    // payload_id, amount, token_address, token_chain, to, to_chain, from_address
+    const HEADER_LEN: usize = 133;
+
+    pub fn from_payload(payload: &[u8]) -> Result<Self> {
+        require!(
+            payload.len() >= Self::HEADER_LEN && payload[0] == 3,
+            XBridgeErrorCode::InvalidWormholeVaa
+        );
+        // normalized amounts fit in u64
+        require!(payload[1..25].iter().all(|b| *b == 0), XBridgeErrorCode::InvalidWormholeVaa);
+        Ok(WormholeTransferWithPayload {
+            amount: u64::from_be_bytes(payload[25..33].try_into().unwrap()),
+            token_address: payload[33..65].try_into().unwrap(),
+            token_chain: u16::from_be_bytes(payload[65..67].try_into().unwrap()),
+            to: Pubkey::try_from(&payload[67..99]).unwrap(),
+            to_chain: u16::from_be_bytes(payload[99..101].try_into().unwrap()),
+            payload: payload[Self::HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bridge_message(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i / 32 + 1) as u8).collect()
+    }
+
+    #[test]
+    fn bridge_message_lengths() {
+        let message = BridgeMessage::from_message(&bridge_message(BridgeMessage::LEN)).unwrap();
+        assert_eq!(message.to_token, [6u8; 32]);
+        assert_eq!(message.refund_address(), Pubkey::from([7u8; 32]));
+
+        let legacy = BridgeMessage::from_message(&bridge_message(BridgeMessage::LEGACY_LEN)).unwrap();
+        assert_eq!(legacy.from_token, [4u8; 32]);
+        assert_eq!(legacy.to_token, [0u8; 32]);
+        assert_eq!(legacy.refund_address(), Pubkey::from([3u8; 32]));
+
+        assert!(BridgeMessage::from_message(&bridge_message(BridgeMessage::LEN - 1)).is_err());
+        assert!(BridgeMessage::from_message(&bridge_message(BridgeMessage::LEN + 32)).is_err());
+    }
+
+    #[test]
+    fn check_to_token() {
+        let message = BridgeMessage::from_message(&bridge_message(BridgeMessage::LEN)).unwrap();
+        message.check_to_token(&Pubkey::from([6u8; 32])).unwrap();
+        assert!(message.check_to_token(&Pubkey::from([5u8; 32])).is_err());
+
+        // a 160 byte message verified before the upgrade is claimable to any destination mint
+        let fixed = BridgeMessage::to_fixed_bytes(&bridge_message(BridgeMessage::LEGACY_LEN)).unwrap();
+        let legacy = BridgeMessage::try_from_slice(&fixed).unwrap();
+        legacy.check_to_token(&Pubkey::from([5u8; 32])).unwrap();
+        legacy.check_to_token(&Pubkey::new_unique()).unwrap();
+    }
+
+    #[test]
+    fn vault_reserves_fit_return_data() {
+        let reserve = VaultReserve {
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            vault_balance: u64::MAX,
+            outstanding: u64::MAX,
+            total_claimed: u64::MAX,
+            total_refunded: u64::MAX,
+        };
+        assert_eq!(reserve.try_to_vec().unwrap().len(), VaultReserve::LEN);
+        let reserves = vec![reserve; VaultReserve::MAX_REPORTED];
+        assert!(reserves.try_to_vec().unwrap().len() <= MAX_RETURN_DATA);
+        let reserves = vec![reserves[0].clone(); VaultReserve::MAX_REPORTED + 1];
+        assert!(reserves.try_to_vec().unwrap().len() > MAX_RETURN_DATA);
+    }
+
+    // MessageV2 header followed by a BurnMessageV2, laid out field by field
+    fn cctp_message(amount: u64, fee_executed: u64, hook_data: &[u8]) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend_from_slice(&1u32.to_be_bytes());            // version
+        message.extend_from_slice(&6u32.to_be_bytes());            // source_domain
+        message.extend_from_slice(&5u32.to_be_bytes());            // destination_domain
+        message.extend_from_slice(&[1u8; 32]);                     // nonce
+        message.extend_from_slice(&[2u8; 32]);                     // sender
+        message.extend_from_slice(&[3u8; 32]);                     // recipient
+        message.extend_from_slice(&[4u8; 32]);                     // destination_caller
+        message.extend_from_slice(&1000u32.to_be_bytes());         // min_finality_threshold
+        message.extend_from_slice(&2000u32.to_be_bytes());         // finality_threshold_executed
+        assert_eq!(message.len(), 148);
+        message.extend_from_slice(&1u32.to_be_bytes());            // burn message version
+        message.extend_from_slice(&[5u8; 32]);                     // burn_token
+        message.extend_from_slice(&[6u8; 32]);                     // mint_recipient
+        message.extend_from_slice(&[0u8; 24]);                     // amount, uint256
+        message.extend_from_slice(&amount.to_be_bytes());
+        message.extend_from_slice(&[7u8; 32]);                     // message_sender
+        message.extend_from_slice(&[0u8; 24]);                     // max_fee, uint256
+        message.extend_from_slice(&500u64.to_be_bytes());
+        message.extend_from_slice(&[0u8; 24]);                     // fee_executed, uint256
+        message.extend_from_slice(&fee_executed.to_be_bytes());
+        message.extend_from_slice(&[0u8; 32]);                     // expiration_block
+        assert_eq!(message.len(), 148 + 228);
+        message.extend_from_slice(hook_data);
+        message
+    }
+
+    #[test]
+    fn cctp_burn_message_offsets() {
+        let message = CctpBurnMessage::from_message(&cctp_message(1_000_000, 300, &[9u8; 72])).unwrap();
+        assert_eq!(message.source_domain, 6);
+        assert_eq!(message.destination_domain, 5);
+        assert_eq!(message.nonce, [1u8; 32]);
+        assert_eq!(message.destination_caller, Pubkey::from([4u8; 32]));
+        assert_eq!(message.burn_token, [5u8; 32]);
+        assert_eq!(message.mint_recipient, Pubkey::from([6u8; 32]));
+        assert_eq!(message.amount, 1_000_000);
+        assert_eq!(message.fee_executed, 300);
+        assert_eq!(message.hook_data, vec![9u8; 72]);
+        assert_eq!(message.received_amount().unwrap(), 999_700);
+
+        let message = CctpBurnMessage::from_message(&cctp_message(100, 300, &[])).unwrap();
+        assert!(message.hook_data.is_empty());
+        assert!(message.received_amount().is_err());
+    }
+
+    #[test]
+    fn cctp_burn_message_rejects() {
+        let message = cctp_message(1_000_000, 300, &[]);
+        assert!(CctpBurnMessage::from_message(&message[..message.len() - 1]).is_err());
+
+        // amount above u64
+        let mut high_amount = message.clone();
+        high_amount[148 + 91] = 1;
+        assert!(CctpBurnMessage::from_message(&high_amount).is_err());
+
+        // fee_executed above u64
+        let mut high_fee = message.clone();
+        high_fee[148 + 164] = 1;
+        assert!(CctpBurnMessage::from_message(&high_fee).is_err());
+    }
+
+    #[test]
+    fn swap_payload() {
+        let mut payload = vec![1u8; 32];
+        payload.extend_from_slice(&[2u8; 32]);
+        payload.extend_from_slice(&990_000u64.to_be_bytes());
+        let swap_payload = SwapPayload::from_payload(&payload).unwrap();
+        assert_eq!(swap_payload.to, Pubkey::from([1u8; 32]));
+        assert_eq!(swap_payload.to_token, Pubkey::from([2u8; 32]));
+        assert_eq!(swap_payload.min_return, 990_000);
+
+        assert!(SwapPayload::from_payload(&payload[..SwapPayload::LEN - 1]).is_err());
+        payload.push(0);
+        assert!(SwapPayload::from_payload(&payload).is_err());
+    }
+
+    // token bridge TransferWithPayload, laid out field by field
+    fn transfer_with_payload(payload: &[u8]) -> Vec<u8> {
+        let mut transfer = vec![3u8];                               // payload_id
+        transfer.extend_from_slice(&[0u8; 24]);                     // amount, uint256
+        transfer.extend_from_slice(&123_456u64.to_be_bytes());
+        transfer.extend_from_slice(&[1u8; 32]);                     // token_address
+        transfer.extend_from_slice(&2u16.to_be_bytes());            // token_chain
+        transfer.extend_from_slice(&[3u8; 32]);                     // to
+        transfer.extend_from_slice(&1u16.to_be_bytes());            // to_chain
+        transfer.extend_from_slice(&[4u8; 32]);                     // from_address
+        assert_eq!(transfer.len(), 133);
+        transfer.extend_from_slice(payload);
+        transfer
+    }
+
+    #[test]
+    fn posted_vaa() {
+        let payload = transfer_with_payload(&[9u8; 72]);
+        let mut data = b"vaa".to_vec();
+        data.push(1);                                               // vaa_version
+        data.push(32);                                              // consistency_level
+        data.extend_from_slice(&1_700_000_000u32.to_le_bytes());    // vaa_time
+        data.extend_from_slice(&[5u8; 32]);                         // vaa_signature_account
+        data.extend_from_slice(&1_700_000_100u32.to_le_bytes());    // submission_time
+        data.extend_from_slice(&7u32.to_le_bytes());                // nonce
+        data.extend_from_slice(&42u64.to_le_bytes());               // sequence
+        data.extend_from_slice(&2u16.to_le_bytes());                // emitter_chain
+        data.extend_from_slice(&[6u8; 32]);                         // emitter_address
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(&payload);
+
+        let vaa = PostedVaa::from_account_data(&data).unwrap();
+        assert_eq!(vaa.vaa_version, 1);
+        assert_eq!(vaa.consistency_level, 32);
+        assert_eq!(vaa.vaa_time, 1_700_000_000);
+        assert_eq!(vaa.vaa_signature_account, Pubkey::from([5u8; 32]));
+        assert_eq!(vaa.submission_time, 1_700_000_100);
+        assert_eq!(vaa.nonce, 7);
+        assert_eq!(vaa.sequence, 42);
+        assert_eq!(vaa.emitter_chain, 2);
+        assert_eq!(vaa.emitter_address, [6u8; 32]);
+        assert_eq!(vaa.payload, payload);
+
+        assert!(PostedVaa::from_account_data(&data[..data.len() - 1]).is_err());
+        let mut wrong_prefix = data.clone();
+        wrong_prefix[..3].copy_from_slice(b"msg");
+        assert!(PostedVaa::from_account_data(&wrong_prefix).is_err());
+        assert!(PostedVaa::from_account_data(b"vaa").is_err());
+    }
+
+    #[test]
+    fn wormhole_transfer_with_payload() {
+        let transfer = WormholeTransferWithPayload::from_payload(&transfer_with_payload(&[9u8; 72])).unwrap();
+        assert_eq!(transfer.amount, 123_456);
+        assert_eq!(transfer.token_address, [1u8; 32]);
+        assert_eq!(transfer.token_chain, 2);
+        assert_eq!(transfer.to, Pubkey::from([3u8; 32]));
+        assert_eq!(transfer.to_chain, 1);
+        assert_eq!(transfer.payload, vec![9u8; 72]);
+
+        let transfer = transfer_with_payload(&[]);
+        assert!(WormholeTransferWithPayload::from_payload(&transfer).unwrap().payload.is_empty());
+        assert!(WormholeTransferWithPayload::from_payload(&transfer[..transfer.len() - 1]).is_err());
+
+        // a plain Transfer, payload id 1
+        let mut wrong_id = transfer.clone();
+        wrong_id[0] = 1;
+        assert!(WormholeTransferWithPayload::from_payload(&wrong_id).is_err());
+
+        // amount above u64
+        let mut high_amount = transfer.clone();
+        high_amount[24] = 1;
+        assert!(WormholeTransferWithPayload::from_payload(&high_amount).is_err());
+    }
+}
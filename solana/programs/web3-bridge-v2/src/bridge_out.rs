@@ -1,11 +1,13 @@
 pub mod adaptors;
 pub mod events;
 pub mod instructions;
+pub mod states;
 pub mod structs;
 
 pub use {
     adaptors::*,
     events::*,
     instructions::*,
+    states::*,
     structs::*,
 };
\ No newline at end of file
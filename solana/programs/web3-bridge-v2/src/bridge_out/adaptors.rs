@@ -1,8 +1,92 @@
+use {
+    crate::{
+        bridge_out::{AdaptorID, BridgeResult, BridgeTo, BridgeToArgs},
+        common::XBridgeErrorCode,
+    },
+    anchor_lang::prelude::*,
+};
+
+pub mod across;
 pub mod cctp;
+pub mod cctp_v2;
 pub mod debridgedln;
+pub mod layerzero_oft;
 pub mod meson;
+pub mod ntt;
 pub mod wormhole;
 pub mod allbridge;
 pub mod wanchain;
+pub mod mayan;
+pub mod mayan_mctp;
 pub mod mayan_swift;
-pub mod bridgers;
\ No newline at end of file
+pub mod mayan_wh_swap;
+pub mod bridgers;
+
+/// A bridge reachable through bridge_to_log.
+/// Each adaptor is a self-contained module, registered by its ID in bridge_to_log_consumed.
+pub trait BridgeAdaptor {
+    /// BridgeToArgs.adaptor_id routed to this adaptor.
+    const ID: AdaptorID;
+    /// Number of remaining_accounts the adaptor reads.
+    const ACCOUNTS_LEN: usize;
+    /// Log the measured balance change instead of BridgeToArgs.amount,
+    /// for bridges that do not consume exactly the requested amount.
+    const MEASURE_AMOUNT: bool = false;
+    /// The bridge's own chain id differs from the logged chain id, bridge_to_log requires a chain_registry.
+    /// Adaptors taking the logged chain id as is may omit it, a given registry is still applied.
+    /// Wormhole, CCTP, Allbridge and Mayan Swift fail with ChainRegistryRequired until set_chain registers their chains.
+    const CHAIN_REGISTRY_REQUIRED: bool = true;
+
+    /// Adaptor args, decoded from BridgeToArgs.data.
+    type Args;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args>;
+
+    /// Checks ctx.remaining_accounts against the adaptor's account constraints before execute,
+    /// the constraints on the adaptor's Accounts struct are not run by Anchor.
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        _data: &BridgeToArgs,
+        _args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)
+    }
+
+    /// Calls the bridge, BridgeResult.ext is logged as LogBridgeToVersion1.ext.
+    /// Adaptors build ext after the bridge call, so it can carry ids the bridge assigns.
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult>;
+
+    /// Token amount expected to leave the user token account for BridgeToArgs.amount.
+    fn expected_consumed(_mint: &Pubkey, amount: u64) -> Result<u64> {
+        Ok(amount)
+    }
+}
+
+pub fn check_accounts_len(accounts: &[AccountInfo], len: usize) -> Result<()> {
+    require!(
+        accounts.len() >= len,
+        XBridgeErrorCode::InvalidAccountsLength
+    );
+    Ok(())
+}
+
+pub fn check_address(account: &AccountInfo, address: &Pubkey) -> Result<()> {
+    require_keys_eq!(account.key(), *address, XBridgeErrorCode::InvalidAccount);
+    Ok(())
+}
+
+// account == PDA(seeds, program_id)
+pub fn check_pda(account: &AccountInfo, seeds: &[&[u8]], program_id: &Pubkey) -> Result<()> {
+    let (pda, _) = Pubkey::try_find_program_address(seeds, program_id)
+        .ok_or(XBridgeErrorCode::InvalidAccount)?;
+    check_address(account, &pda)
+}
+
+pub fn check_writable(account: &AccountInfo) -> Result<()> {
+    require!(account.is_writable, XBridgeErrorCode::InvalidAccount);
+    Ok(())
+}
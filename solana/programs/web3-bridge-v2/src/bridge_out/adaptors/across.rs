@@ -0,0 +1,353 @@
+use {
+    crate::{
+        bridge_out::{
+            check_accounts_len, check_address, check_pda, check_writable,
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs,
+        },
+        common::{across_spoke_pool_program as AcrossSpokePool, vec_to_hex_string, XBridgeErrorCode},
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{instruction::Instruction, program::invoke},
+    },
+    anchor_spl::{
+        associated_token::get_associated_token_address,
+        token::{Mint, TokenAccount},
+    },
+    serde::Serialize,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BridgeToAcrossArgs {
+    pub output_token: Pubkey,           // token received on the destination, left-padded for EVM chains
+    pub output_amount: [u8; 32],        // uint256 (big-endian) amount received on the destination
+    pub exclusive_relayer: Pubkey,      // default pubkey lets any relayer fill
+    pub quote_timestamp: u32,           // timestamp of the Across quote the fees are based on
+    pub fill_deadline: u32,             // deposit is refunded if not filled before this timestamp
+    pub exclusivity_parameter: u32,     // exclusivity period, offset or deadline as defined by Across
+    pub message: Vec<u8>,               // message handled by the recipient on the destination
+}
+
+impl BridgeToAcrossArgs {
+    pub fn try_from_vec(data: &[u8]) -> Result<BridgeToAcrossArgs> {
+        let decoded_args = BridgeToAcrossArgs::try_from_slice(&data)?;
+        Ok(decoded_args)
+    }
+}
+
+// deposit params of the svm spoke pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AcrossDepositParams {
+    pub depositor: Pubkey,
+    pub recipient: Pubkey,
+    pub input_token: Pubkey,
+    pub output_token: Pubkey,
+    pub input_amount: u64,
+    pub output_amount: [u8; 32],
+    pub destination_chain_id: u64,
+    pub exclusive_relayer: Pubkey,
+    pub quote_timestamp: u32,
+    pub fill_deadline: u32,
+    pub exclusivity_parameter: u32,
+    pub message: Vec<u8>,
+}
+
+// State.seed and State.number_of_deposits of the svm spoke pool, after the discriminator,
+// paused_deposits, paused_fills and owner
+const STATE_SEED_OFFSET: usize = 8 + 1 + 1 + 32;
+const STATE_DEPOSITS_OFFSET: usize = STATE_SEED_OFFSET + 8;
+
+fn state_seed(state: &AccountInfo) -> Result<u64> {
+    let data = state.try_borrow_data()?;
+    require!(data.len() >= STATE_DEPOSITS_OFFSET + 4, XBridgeErrorCode::InvalidAccount);
+    Ok(u64::from_le_bytes(<[u8; 8]>::try_from(&data[STATE_SEED_OFFSET..STATE_DEPOSITS_OFFSET]).unwrap()))
+}
+
+// the spoke pool increments number_of_deposits and uses it as the deposit id,
+// read after the deposit it is the id of that deposit
+fn deposit_id(state: &AccountInfo) -> Result<u32> {
+    let data = state.try_borrow_data()?;
+    require!(data.len() >= STATE_DEPOSITS_OFFSET + 4, XBridgeErrorCode::InvalidAccount);
+    Ok(u32::from_le_bytes(
+        <[u8; 4]>::try_from(&data[STATE_DEPOSITS_OFFSET..STATE_DEPOSITS_OFFSET + 4]).unwrap()
+    ))
+}
+
+#[derive(Accounts)]
+pub struct BridgeToAcross<'info> {
+    #[account(mut)]
+    /// Payer.
+    /// Depositor of the bridged tokens.
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    /// State Account
+    /// PDA: seeds = [b"state", seed], seeds::program = "across_spoke_pool_program".
+    /// Approved as the delegate of user_token_account for the deposit.
+    /// CHECK: This account is a PDA account, which is used for across.
+    pub state: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// User Token Account
+    /// ATA: mint = mint, owner = payer,
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    /// Vault Account
+    /// ATA: mint = mint, owner = state.
+    /// CHECK: This account is an ATA account, which is used for across.
+    pub vault: AccountInfo<'info>,
+
+    /// tokenMint
+    /// give token mint
+    pub mint: Account<'info, Mint>,
+
+    /// SPL Token Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    /// CHECK: fixed
+    pub token_program: AccountInfo<'info>,
+
+    /// Associated Token Program
+    /// "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+    /// CHECK: fixed
+    pub associated_token_program: AccountInfo<'info>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: AccountInfo<'info>,
+
+    /// Event Authority Account
+    /// PDA: seeds = [b"__event_authority"], seeds::program = "across_spoke_pool_program".
+    /// CHECK: This account is a PDA account, which is used for across.
+    pub event_authority: AccountInfo<'info>,
+
+    /// Across Spoke Pool Program
+    /// "DLv3NggMiSaef97YCkew5xKUHDh13tVGZ7tydt3ZeAru"
+    /// CHECK: fixed
+    pub spoke_pool_program: AccountInfo<'info>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AcrossExtLog {
+    deposit_id: u32,
+    quote_timestamp: u32,
+    fill_deadline: u32,
+    output_token: String,
+    output_amount: String,
+}
+
+/// to_chain_id is the Across chain id, which is already the EVM chain id for EVM destinations,
+/// e.g. 1, 10, 137, 8453, 42161, 59144, so chain_registry is optional.
+pub struct AcrossAdaptor;
+
+impl BridgeAdaptor for AcrossAdaptor {
+    const ID: AdaptorID = AdaptorID::ACROSS;
+    const ACCOUNTS_LEN: usize = 4;
+    const CHAIN_REGISTRY_REQUIRED: bool = false;
+    type Args = BridgeToAcrossArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToAcrossArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        require!(data.to.len() == 32, XBridgeErrorCode::InvalidRecipientLength);
+        require!(
+            args.fill_deadline as i64 > Clock::get()?.unix_timestamp,
+            XBridgeErrorCode::InvalidFillDeadline
+        );
+        let accounts = ctx.remaining_accounts;
+        let spoke_pool = AcrossSpokePool::id();
+        // state, PDA: seeds = [b"state", seed]
+        require_keys_eq!(*accounts[0].owner, spoke_pool, XBridgeErrorCode::InvalidAccount);
+        check_pda(&accounts[0], &[b"state", &state_seed(&accounts[0])?.to_le_bytes()], &spoke_pool)?;
+        check_writable(&accounts[0])?;
+        // vault, ATA: mint = mint, owner = state
+        check_address(&accounts[1], &get_associated_token_address(accounts[0].key, &ctx.accounts.mint.key()))?;
+        check_writable(&accounts[1])?;
+        // event_authority, PDA: seeds = [b"__event_authority"]
+        check_pda(&accounts[2], &[b"__event_authority"], &spoke_pool)?;
+        check_address(&accounts[3], &spoke_pool)
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    data: BridgeToArgs,
+    bridge_to_across_args: BridgeToAcrossArgs,
+) -> Result<BridgeResult> {
+    let params = AcrossDepositParams {
+        depositor: ctx.accounts.payer.key(),
+        recipient: Pubkey::try_from(data.to.as_slice())
+            .map_err(|_| XBridgeErrorCode::InvalidRecipientLength)?,
+        input_token: ctx.accounts.mint.key(),
+        output_token: bridge_to_across_args.output_token,
+        input_amount: data.amount,
+        output_amount: bridge_to_across_args.output_amount,
+        destination_chain_id: data.to_chain_id,
+        exclusive_relayer: bridge_to_across_args.exclusive_relayer,
+        quote_timestamp: bridge_to_across_args.quote_timestamp,
+        fill_deadline: bridge_to_across_args.fill_deadline,
+        exclusivity_parameter: bridge_to_across_args.exclusivity_parameter,
+        message: bridge_to_across_args.message,
+    };
+
+    // deposit
+    let mut new_data = vec![242u8, 35u8, 198u8, 137u8, 82u8, 225u8, 242u8, 182u8];
+    new_data.extend_from_slice(&params.try_to_vec()?);
+
+    let bridge_to_across = BridgeToAcross {
+        // account in bridge_to.accounts
+        payer: ctx.accounts.payer.clone(),
+        mint: ctx.accounts.mint.clone(),
+        user_token_account: ctx.accounts.user_token_account.clone(),
+        token_program: ctx.accounts.token_program.clone(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.clone(),
+        // account in bridge_to.remaining_accounts
+        state: ctx.remaining_accounts[0].to_account_info(),
+        vault: ctx.remaining_accounts[1].to_account_info(),
+        event_authority: ctx.remaining_accounts[2].to_account_info(),
+        spoke_pool_program: ctx.remaining_accounts[3].to_account_info(),
+    };
+
+    // the spoke pool moves the deposit into the vault as a delegate, signed by the state PDA
+    anchor_spl::token::approve(
+        CpiContext::new(
+            bridge_to_across.token_program.to_account_info(),
+            anchor_spl::token::Approve {
+                to: bridge_to_across.user_token_account.to_account_info(),
+                delegate: bridge_to_across.state.to_account_info(),
+                authority: bridge_to_across.payer.to_account_info(),
+            },
+        ),
+        data.amount,
+    )?;
+
+    let ix = Instruction {
+        program_id: AcrossSpokePool::id(),
+        data: new_data,
+        accounts: vec![
+            AccountMeta::new(bridge_to_across.payer.key(), true),
+            AccountMeta::new(bridge_to_across.state.key(), false),
+            AccountMeta::new(bridge_to_across.user_token_account.key(), false),
+            AccountMeta::new(bridge_to_across.vault.key(), false),
+            AccountMeta::new_readonly(bridge_to_across.mint.key(), false),
+            AccountMeta::new_readonly(bridge_to_across.token_program.key(), false),
+            AccountMeta::new_readonly(bridge_to_across.associated_token_program.key(), false),
+            AccountMeta::new_readonly(bridge_to_across.system_program.key(), false),
+            AccountMeta::new_readonly(bridge_to_across.event_authority.key(), false),
+            AccountMeta::new_readonly(bridge_to_across.spoke_pool_program.key(), false),
+        ],
+    };
+
+    invoke(
+        &ix,
+        &bridge_to_across.to_account_infos()
+    )?;
+
+    let across_log = AcrossExtLog {
+        deposit_id: deposit_id(&bridge_to_across.state)?,
+        quote_timestamp: bridge_to_across_args.quote_timestamp,
+        fill_deadline: bridge_to_across_args.fill_deadline,
+        output_token: "0x".to_string() + &vec_to_hex_string(bridge_to_across_args.output_token.to_bytes().to_vec()),
+        output_amount: "0x".to_string() + &vec_to_hex_string(bridge_to_across_args.output_amount.to_vec()),
+    };
+    Ok(BridgeResult{
+        ext: serde_json::to_string(&across_log).unwrap(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::solana_program::hash as hash;
+
+    #[test]
+    fn sighash() {
+        let mut sighash = [0u8; 8];
+        sighash.copy_from_slice(&hash::hash(b"global:deposit").to_bytes()[..8]);
+        assert_eq!(sighash, [242u8, 35u8, 198u8, 137u8, 82u8, 225u8, 242u8, 182u8]);
+    }
+
+    #[test]
+    fn encode_params() {
+        let params = AcrossDepositParams {
+            depositor: Pubkey::default(),
+            recipient: Pubkey::default(),
+            input_token: Pubkey::default(),
+            output_token: Pubkey::default(),
+            input_amount: 1_000_000,
+            output_amount: [0; 32],
+            destination_chain_id: 42161,
+            exclusive_relayer: Pubkey::default(),
+            quote_timestamp: 0,
+            fill_deadline: 0,
+            exclusivity_parameter: 0,
+            message: vec![],
+        };
+        // 4 * Pubkey + u64 + [u8; 32] + u64 + Pubkey + 3 * u32 + empty vec
+        assert_eq!(params.try_to_vec().unwrap().len(), 128 + 8 + 32 + 8 + 32 + 12 + 4);
+    }
+
+    // State of the svm spoke pool
+    #[derive(AnchorSerialize)]
+    struct SpokePoolState {
+        paused_deposits: bool,
+        paused_fills: bool,
+        owner: Pubkey,
+        seed: u64,
+        number_of_deposits: u32,
+        chain_id: u64,
+        current_time: u32,
+        remote_domain: u32,
+        cross_domain_admin: Pubkey,
+        root_bundle_id: u32,
+        deposit_quote_time_buffer: u32,
+        fill_deadline_buffer: u32,
+    }
+
+    #[test]
+    fn state_offsets() {
+        let state = SpokePoolState {
+            paused_deposits: true,
+            paused_fills: true,
+            owner: Pubkey::new_unique(),
+            seed: 0x0102030405060708,
+            number_of_deposits: 42,
+            chain_id: 34268394551451,
+            current_time: 1,
+            remote_domain: 0,
+            cross_domain_admin: Pubkey::new_unique(),
+            root_bundle_id: 3,
+            deposit_quote_time_buffer: 3600,
+            fill_deadline_buffer: 21600,
+        };
+        let mut data = vec![0u8; 8];
+        data.extend_from_slice(&state.try_to_vec().unwrap());
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &AcrossSpokePool::ID, false, 0);
+        assert_eq!(state_seed(&info).unwrap(), 0x0102030405060708);
+        assert_eq!(deposit_id(&info).unwrap(), 42);
+
+        let mut short = vec![0u8; STATE_DEPOSITS_OFFSET + 3];
+        let short_info = AccountInfo::new(&key, false, true, &mut lamports, &mut short, &AcrossSpokePool::ID, false, 0);
+        assert!(deposit_id(&short_info).is_err());
+    }
+}
@@ -1,9 +1,12 @@
 use {
     crate::{
-        bridge_out::{BridgeResult, BridgeTo, BridgeToArgs},
+        bridge_out::{
+            check_accounts_len, check_address, check_pda, check_writable,
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs,
+        },
         common::{
             allbridge_gas_program as GasProgram, allbridge_messager_program as MessagerProgram,
-            allbridge_program as AllBridgeProgram, AllBridgeErrorCode,
+            allbridge_program as AllBridgeProgram, AllBridgeErrorCode, XBridgeErrorCode,
         },
     },
     anchor_lang::{
@@ -15,6 +18,73 @@ use {
 
 pub const CHAIN_ID: u8 = 4;
 
+pub struct AllbridgeAdaptor;
+
+impl BridgeAdaptor for AllbridgeAdaptor {
+    const ID: AdaptorID = AdaptorID::ALLBRIDGE;
+    const ACCOUNTS_LEN: usize = 14;
+    // The actual amount deducted for the allbridge bridge is not equal to the amount entered, due to the mechanism of the bridge
+    const MEASURE_AMOUNT: bool = true;
+    type Args = BridgeToAllbridgeArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToAllbridgeArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        let accounts = ctx.remaining_accounts;
+        let allbridge = AllBridgeProgram::id();
+        let messenger = MessagerProgram::id();
+        let gas = GasProgram::id();
+        let to_chain_id = [u8::try_from(data.to_chain_id).map_err(|_| XBridgeErrorCode::InvalidToChainId)?];
+        // lock, PDA: seeds = [b"lock", nonce]
+        check_pda(&accounts[0], &[b"lock", &args.nonce], &allbridge)?;
+        check_writable(&accounts[0])?;
+        // config, PDA: seeds = [b"config"]
+        check_pda(&accounts[1], &[b"config"], &allbridge)?;
+        check_writable(&accounts[1])?;
+        // other_bridge_token, PDA: seeds = [b"other_bridge_token", to_chain_id, receive_token]
+        check_pda(&accounts[2], &[b"other_bridge_token", &to_chain_id, &args.receive_token], &allbridge)?;
+        check_writable(&accounts[2])?;
+        check_address(&accounts[3], &messenger)?;
+        // messenger_config, PDA: seeds = [b"config"], messenger
+        check_pda(&accounts[4], &[b"config"], &messenger)?;
+        check_writable(&accounts[4])?;
+        // sent_message_account, PDA: seeds = [b"sent_message", message_with_signer], messenger
+        check_pda(&accounts[5], &[b"sent_message", &args.message_with_signer], &messenger)?;
+        check_writable(&accounts[5])?;
+        // messenger_gas_usage, PDA: seeds = [b"gas_usage", to_chain_id], messenger
+        check_pda(&accounts[6], &[b"gas_usage", &to_chain_id], &messenger)?;
+        // pool
+        check_writable(&accounts[7])?;
+        // bridge_token, PDA: seeds = [b"token", mint]
+        check_pda(&accounts[8], &[b"token", ctx.accounts.mint.key().as_ref()], &allbridge)?;
+        check_writable(&accounts[8])?;
+        // gas_price, PDA: seeds = [b"price_v2", to_chain_id], gas_program
+        check_pda(&accounts[9], &[b"price_v2", &to_chain_id], &gas)?;
+        // this_gas_price, PDA: seeds = [b"price_v2", CHAIN_ID], gas_program
+        check_pda(&accounts[10], &[b"price_v2", &[CHAIN_ID]], &gas)?;
+        // chain_bridge, PDA: seeds = [b"chain_bridge", to_chain_id]
+        check_pda(&accounts[11], &[b"chain_bridge", &to_chain_id], &allbridge)?;
+        // bridge_authority, PDA: seeds = [config]
+        check_pda(&accounts[12], &[accounts[1].key.as_ref()], &allbridge)?;
+        check_address(&accounts[13], &allbridge)
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
     data: BridgeToArgs,
@@ -87,7 +157,7 @@ pub fn handler<'info>(
         &bridge_to_allbridge.to_account_infos()
     )?;
 
-    Ok(BridgeResult {
+    Ok(BridgeResult{
         ext: "".to_string(),
     })
 }
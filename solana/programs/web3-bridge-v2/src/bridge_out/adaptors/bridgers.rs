@@ -1,11 +1,12 @@
 use {
     crate::{
         bridge_out::{
-            BridgeResult, BridgeTo, BridgeToArgs
+            check_accounts_len, check_address, check_writable,
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs
         },
         common::{
             bridgers_program, bridgers_vs_info, wrapped_sol,
-            BridgersErrorCode
+            BridgersErrorCode, XBridgeErrorCode
         }
     },
     anchor_lang::{
@@ -138,6 +139,49 @@ pub struct BridgeToBridgersSol<'info> {
 
 
 
+pub struct BridgersAdaptor;
+
+impl BridgeAdaptor for BridgersAdaptor {
+    const ID: AdaptorID = AdaptorID::BRIDGERS;
+    const ACCOUNTS_LEN: usize = 3;
+    const CHAIN_REGISTRY_REQUIRED: bool = false;
+    type Args = BridgeToBridgersArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToBridgersArgs::try_from_vec(&data.data)
+    }
+
+    // the SOL transfer also reads rent_sysvar and pda_account_info
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        _data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        let accounts_len = if args._selector_id == 0x03 { 5 } else { Self::ACCOUNTS_LEN };
+        check_accounts_len(ctx.remaining_accounts, accounts_len)?;
+        let accounts = ctx.remaining_accounts;
+        check_address(&accounts[0], &bridgers_program::id())?;
+        // dest_token_info
+        check_writable(&accounts[1])?;
+        check_address(&accounts[2], &bridgers_vs_info::id())?;
+        if args._selector_id == 0x03 {
+            require_keys_eq!(ctx.accounts.mint.key(), wrapped_sol::id(), XBridgeErrorCode::InvalidAccount);
+            check_address(&accounts[3], &sysvar::rent::ID)?;
+            // pda_account_info
+            check_writable(&accounts[4])?;
+        }
+        Ok(())
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
 pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: BridgeToArgs, bridge_to_bridgers_args: BridgeToBridgersArgs) -> Result<BridgeResult> {
 
     let mut new_data = Vec::new();
@@ -1,14 +1,21 @@
 use {
     crate::{
         bridge_out::{
+            check_accounts_len,
+            check_address,
+            check_pda,
+            check_writable,
+            AdaptorID,
+            BridgeAdaptor,
+            BridgeResult,
             BridgeTo,
             BridgeToArgs,
             RelayerFee,
-            BridgeResult,
         },
         common::{
             cctp_message_program as CCTPMessage, 
             cctp_program as CCTP, 
+            XBridgeErrorCode,
         }
     },
     anchor_lang::{
@@ -198,12 +205,71 @@ pub struct CrossChainData{
     mint_recipient: Pubkey,
 }
 
-pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, mut data: BridgeToArgs ) -> Result<BridgeResult> {
+pub struct CctpAdaptor;
+
+impl BridgeAdaptor for CctpAdaptor {
+    const ID: AdaptorID = AdaptorID::CCTP;
+    const ACCOUNTS_LEN: usize = 10;
+    // the redeem fee, absent in the version with no redeem fee
+    type Args = Option<RedeemToCctpArgs>;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        Ok(RedeemToCctpArgs::try_from_vec(&data.data).ok())
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        // the redeem fee is paid to remaining_accounts[10]
+        let accounts_len = if args.is_some() { Self::ACCOUNTS_LEN + 1 } else { Self::ACCOUNTS_LEN };
+        check_accounts_len(ctx.remaining_accounts, accounts_len)?;
+        let accounts = ctx.remaining_accounts;
+        let token_messenger_minter = CCTP::id();
+        let message_transmitter = CCTPMessage::id();
+        let destination_domain = (data.to_chain_id as u32).to_string();
+        // sender_authority_pda, PDA: seeds = [b"sender_authority"]
+        check_pda(&accounts[0], &[b"sender_authority"], &token_messenger_minter)?;
+        // message_transmitter, PDA: seeds = [b"message_transmitter"], message_transmitter_program
+        check_pda(&accounts[1], &[b"message_transmitter"], &message_transmitter)?;
+        check_writable(&accounts[1])?;
+        // token_messenger, PDA: seeds = [b"token_messenger"]
+        check_pda(&accounts[2], &[b"token_messenger"], &token_messenger_minter)?;
+        // remote_token_messenger, PDA: seeds = [b"remote_token_messenger", destination_domain]
+        check_pda(&accounts[3], &[b"remote_token_messenger", destination_domain.as_bytes()], &token_messenger_minter)?;
+        // token_minter, PDA: seeds = [b"token_minter"]
+        check_pda(&accounts[4], &[b"token_minter"], &token_messenger_minter)?;
+        // local_token, PDA: seeds = [b"local_token", mint]
+        check_pda(&accounts[5], &[b"local_token", ctx.accounts.mint.key().as_ref()], &token_messenger_minter)?;
+        check_writable(&accounts[5])?;
+        // message_sent_event_data, a new keypair account signed by the user
+        check_writable(&accounts[6])?;
+        require!(accounts[6].is_signer, XBridgeErrorCode::InvalidAccount);
+        check_address(&accounts[7], &message_transmitter)?;
+        check_address(&accounts[8], &token_messenger_minter)?;
+        // event_authority, PDA: seeds = [b"__event_authority"]
+        check_pda(&accounts[9], &[b"__event_authority"], &token_messenger_minter)?;
+        if args.is_some() {
+            // redeem fee receiver
+            check_writable(&accounts[10])?;
+        }
+        Ok(())
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, mut data: BridgeToArgs, redeem_fee: Option<RedeemToCctpArgs>) -> Result<BridgeResult> {
 
     // redeem fee
-    let redeem_fee_wrap = RedeemToCctpArgs::try_from_vec(&data.data);
-    if redeem_fee_wrap.is_ok() {
-        let redeem_fee = redeem_fee_wrap.unwrap();
+    if let Some(redeem_fee) = redeem_fee {
         let cpi_context = CpiContext::new(
             ctx.accounts.mint.to_account_info(),
             Transfer {
@@ -0,0 +1,325 @@
+use {
+    crate::{
+        bridge_out::{
+            check_accounts_len, check_address, check_pda, check_writable,
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs,
+        },
+        common::{
+            cctp_v2_message_program as CCTPV2Message,
+            cctp_v2_program as CCTPV2,
+            vec_to_hex_string, XBridgeErrorCode,
+        }
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{instruction::Instruction, program::invoke},
+    },
+    anchor_spl::token::{Mint, TokenAccount},
+    serde::Serialize,
+};
+
+// min_finality_threshold accepted by CCTP v2, below FINALITY_STANDARD the burn is a fast transfer
+pub const FINALITY_FAST: u32 = 1000;
+pub const FINALITY_STANDARD: u32 = 2000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BridgeToCctpV2Args {
+    pub destination_caller: Pubkey,     // default pubkey lets any caller receive the message
+    pub max_fee: u64,                   // max fee paid to the fast transfer, in the burned token
+    pub min_finality_threshold: u32,    // FINALITY_FAST or FINALITY_STANDARD
+    pub hook_data: Vec<u8>,             // non-empty burns with deposit_for_burn_with_hook
+}
+
+impl BridgeToCctpV2Args {
+    pub fn try_from_vec(data: &[u8]) -> Result<BridgeToCctpV2Args> {
+        let decoded_args = BridgeToCctpV2Args::try_from_slice(&data)?;
+        Ok(decoded_args)
+    }
+}
+
+// DepositForBurnParams of token_messenger_minter_v2
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct DepositForBurnV2Params {
+    pub amount: u64,
+    pub destination_domain: u32,
+    pub mint_recipient: Pubkey,
+    pub destination_caller: Pubkey,
+    pub max_fee: u64,
+    pub min_finality_threshold: u32,
+}
+
+#[derive(Accounts)]
+pub struct BridgeToCctpV2<'info> {
+    #[account(mut)]
+    /// Payer.
+    /// Owner of the burned tokens and payer of the message_sent_event_data rent.
+    pub payer: Signer<'info>,
+
+    /// Token Message Account
+    /// PDA: seeds = [b"sender_authority"], seeds::program = "cctp_v2_program".
+    /// CHECK: This account is a PDA account, which is used for cctp v2.
+    pub sender_authority_pda: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// User Token Account
+    /// ATA: mint = mint, owner = payer,
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// Denylist Account
+    /// PDA: seeds = [b"denylist_account", payer], seeds::program = "cctp_v2_program".
+    /// CHECK: This account is a PDA account, which is used for cctp v2.
+    pub denylist_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Message Transmitter Account
+    /// PDA: seeds = [b"message_transmitter"], seeds::program = "cctp_v2_message_program".
+    /// CHECK: This account is a PDA account, which is used for cctp v2.
+    pub message_transmitter: AccountInfo<'info>,
+
+    /// Token Message Account
+    /// PDA: seeds = [b"token_messenger"], seeds::program = "cctp_v2_program".
+    /// CHECK: This account is a PDA account, which is used for cctp v2.
+    pub token_messenger: AccountInfo<'info>,
+
+    /// Remote Token Account
+    /// PDA: seeds = [b"remote_token_messenger", dest_domain.to_string()], seeds::program = "cctp_v2_program".
+    /// CHECK: This account is a PDA account, which is used for cctp v2.
+    pub remote_token_messenger: AccountInfo<'info>,
+
+    /// Token Minter Account
+    /// PDA: seeds = [b"token_minter"], seeds::program = "cctp_v2_program".
+    /// CHECK: This account is a PDA account, which is used for cctp v2.
+    pub token_minter: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Local Token Account
+    /// PDA: seeds = [b"local_token", mint.key()], seeds::program = "cctp_v2_program".
+    /// CHECK: This account is a PDA account, which is used for cctp v2.
+    pub local_token: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// tokenMint
+    /// give token mint
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    /// CHECK: Account to store MessageSent event data in. Any non-PDA uninitialized address.
+    pub message_sent_event_data: AccountInfo<'info>,
+
+    /// message_transmitter_v2 Program ID
+    /// "CCTPV2Sm4AdWt5296sk4P1tnNmRvzJ3CiEodH5uSFe9K"
+    /// CHECK: fixed
+    pub message_transmitter_program: AccountInfo<'info>,
+
+    /// token_messenger_minter_v2 Program ID
+    /// "CCTPV2vPZJS2u2BBsUoscuikbYjnpFmbFsvVuJdgUMQe"
+    /// CHECK: fixed
+    pub token_messenger_minter_program: AccountInfo<'info>,
+
+    /// SPL Token Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    /// CHECK: fixed
+    pub token_program: AccountInfo<'info>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: AccountInfo<'info>,
+
+    /// Event Authority Account
+    /// PDA: seeds = [b"__event_authority"], seeds::program = "cctp_v2_program".
+    /// CHECK: This account is a PDA account, which is used for cctp v2.
+    pub event_authority: AccountInfo<'info>,
+}
+
+// the v2 nonce is assigned by the attestation service, the message is found by message_sent_event_data
+#[derive(Debug, Clone, Serialize)]
+pub struct CctpV2ExtLog {
+    message_sent_event_data: String,
+    destination_domain: u32,
+    destination_caller: String,
+    max_fee: u64,
+    min_finality_threshold: u32,
+    hook_data: String,
+}
+
+/// to_chain_id is the CCTP domain, mapped by chain_registry:
+/// 0 => 1, 1 => 43114, 2 => 10, 3 => 42161, 6 => 8453, 7 => 137, 10 => 130, 11 => 59144, 13 => 146, 14 => 480.
+pub struct CctpV2Adaptor;
+
+impl BridgeAdaptor for CctpV2Adaptor {
+    const ID: AdaptorID = AdaptorID::CCTP_V2;
+    const ACCOUNTS_LEN: usize = 11;
+    type Args = BridgeToCctpV2Args;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToCctpV2Args::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        require!(
+            args.min_finality_threshold == FINALITY_FAST || args.min_finality_threshold == FINALITY_STANDARD,
+            XBridgeErrorCode::InvalidFinalityThreshold
+        );
+        require!(args.max_fee < data.amount, XBridgeErrorCode::InvalidMaxFee);
+        let accounts = ctx.remaining_accounts;
+        let token_messenger_minter = CCTPV2::id();
+        let message_transmitter = CCTPV2Message::id();
+        let destination_domain = u32::try_from(data.to_chain_id)
+            .map_err(|_| XBridgeErrorCode::InvalidToChainId)?
+            .to_string();
+        // sender_authority_pda, PDA: seeds = [b"sender_authority"]
+        check_pda(&accounts[0], &[b"sender_authority"], &token_messenger_minter)?;
+        // denylist_account, PDA: seeds = [b"denylist_account", payer]
+        check_pda(&accounts[1], &[b"denylist_account", ctx.accounts.payer.key().as_ref()], &token_messenger_minter)?;
+        // message_transmitter, PDA: seeds = [b"message_transmitter"], message_transmitter_program
+        check_pda(&accounts[2], &[b"message_transmitter"], &message_transmitter)?;
+        check_writable(&accounts[2])?;
+        // token_messenger, PDA: seeds = [b"token_messenger"]
+        check_pda(&accounts[3], &[b"token_messenger"], &token_messenger_minter)?;
+        // remote_token_messenger, PDA: seeds = [b"remote_token_messenger", destination_domain]
+        check_pda(&accounts[4], &[b"remote_token_messenger", destination_domain.as_bytes()], &token_messenger_minter)?;
+        // token_minter, PDA: seeds = [b"token_minter"]
+        check_pda(&accounts[5], &[b"token_minter"], &token_messenger_minter)?;
+        // local_token, PDA: seeds = [b"local_token", mint]
+        check_pda(&accounts[6], &[b"local_token", ctx.accounts.mint.key().as_ref()], &token_messenger_minter)?;
+        check_writable(&accounts[6])?;
+        // message_sent_event_data, a new keypair account signed by the user
+        check_writable(&accounts[7])?;
+        require!(accounts[7].is_signer, XBridgeErrorCode::InvalidAccount);
+        check_address(&accounts[8], &message_transmitter)?;
+        check_address(&accounts[9], &token_messenger_minter)?;
+        // event_authority, PDA: seeds = [b"__event_authority"]
+        check_pda(&accounts[10], &[b"__event_authority"], &token_messenger_minter)
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    data: BridgeToArgs,
+    bridge_to_cctp_v2_args: BridgeToCctpV2Args,
+) -> Result<BridgeResult> {
+    let params = DepositForBurnV2Params {
+        amount: data.amount,
+        destination_domain: data.to_chain_id as u32,
+        mint_recipient: Pubkey::try_from(data.to.as_slice())
+            .map_err(|_| XBridgeErrorCode::InvalidRecipientLength)?,
+        destination_caller: bridge_to_cctp_v2_args.destination_caller,
+        max_fee: bridge_to_cctp_v2_args.max_fee,
+        min_finality_threshold: bridge_to_cctp_v2_args.min_finality_threshold,
+    };
+
+    // deposit_for_burn / deposit_for_burn_with_hook
+    let mut new_data = if bridge_to_cctp_v2_args.hook_data.is_empty() {
+        vec![215u8, 60u8, 61u8, 46u8, 114u8, 55u8, 128u8, 176u8]
+    } else {
+        vec![111u8, 245u8, 62u8, 131u8, 204u8, 108u8, 223u8, 155u8]
+    };
+    new_data.extend_from_slice(&params.try_to_vec()?);
+    if !bridge_to_cctp_v2_args.hook_data.is_empty() {
+        new_data.extend_from_slice(&bridge_to_cctp_v2_args.hook_data.try_to_vec()?);
+    }
+
+    let bridge_to_cctp_v2 = BridgeToCctpV2 {
+        // account in bridge_to.accounts
+        payer: ctx.accounts.payer.clone(),
+        mint: ctx.accounts.mint.clone(),
+        user_token_account: ctx.accounts.user_token_account.clone(),
+        token_program: ctx.accounts.token_program.clone(),
+        system_program: ctx.accounts.system_program.clone(),
+        // account in bridge_to.remaining_accounts
+        sender_authority_pda: ctx.remaining_accounts[0].to_account_info(),
+        denylist_account: ctx.remaining_accounts[1].to_account_info(),
+        message_transmitter: ctx.remaining_accounts[2].to_account_info(),
+        token_messenger: ctx.remaining_accounts[3].to_account_info(),
+        remote_token_messenger: ctx.remaining_accounts[4].to_account_info(),
+        token_minter: ctx.remaining_accounts[5].to_account_info(),
+        local_token: ctx.remaining_accounts[6].to_account_info(),
+        message_sent_event_data: ctx.remaining_accounts[7].to_account_info(),
+        message_transmitter_program: ctx.remaining_accounts[8].to_account_info(),
+        token_messenger_minter_program: ctx.remaining_accounts[9].to_account_info(),
+        event_authority: ctx.remaining_accounts[10].to_account_info(),
+    };
+
+    let ix = Instruction {
+        program_id: CCTPV2::id(),
+        data: new_data,
+        accounts: vec![
+            AccountMeta::new_readonly(bridge_to_cctp_v2.payer.key(), true),
+            AccountMeta::new(bridge_to_cctp_v2.payer.key(), true),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.sender_authority_pda.key(), false),
+            AccountMeta::new(bridge_to_cctp_v2.user_token_account.key(), false),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.denylist_account.key(), false),
+            AccountMeta::new(bridge_to_cctp_v2.message_transmitter.key(), false),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.token_messenger.key(), false),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.remote_token_messenger.key(), false),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.token_minter.key(), false),
+            AccountMeta::new(bridge_to_cctp_v2.local_token.key(), false),
+            AccountMeta::new(bridge_to_cctp_v2.mint.key(), false),
+            AccountMeta::new(bridge_to_cctp_v2.message_sent_event_data.key(), true),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.message_transmitter_program.key(), false),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.token_messenger_minter_program.key(), false),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.token_program.key(), false),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.system_program.key(), false),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.event_authority.key(), false),
+            AccountMeta::new_readonly(bridge_to_cctp_v2.token_messenger_minter_program.key(), false),
+        ],
+    };
+
+    invoke(
+        &ix,
+        &bridge_to_cctp_v2.to_account_infos()
+    )?;
+
+    let cctp_v2_log = CctpV2ExtLog {
+        message_sent_event_data: bridge_to_cctp_v2.message_sent_event_data.key().to_string(),
+        destination_domain: params.destination_domain,
+        destination_caller: bridge_to_cctp_v2_args.destination_caller.to_string(),
+        max_fee: bridge_to_cctp_v2_args.max_fee,
+        min_finality_threshold: bridge_to_cctp_v2_args.min_finality_threshold,
+        hook_data: "0x".to_string() + &vec_to_hex_string(bridge_to_cctp_v2_args.hook_data.clone()),
+    };
+    Ok(BridgeResult{
+        ext: serde_json::to_string(&cctp_v2_log).unwrap(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::solana_program::hash as hash;
+
+    #[test]
+    fn sighash() {
+        let mut sighash = [0u8; 8];
+        sighash.copy_from_slice(&hash::hash(b"global:deposit_for_burn_with_hook").to_bytes()[..8]);
+        assert_eq!(sighash, [111u8, 245u8, 62u8, 131u8, 204u8, 108u8, 223u8, 155u8]);
+    }
+
+    #[test]
+    fn encode_params() {
+        let params = DepositForBurnV2Params {
+            amount: 1_000_000,
+            destination_domain: 6,
+            mint_recipient: Pubkey::default(),
+            destination_caller: Pubkey::default(),
+            max_fee: 100,
+            min_finality_threshold: FINALITY_FAST,
+        };
+        // u64 + u32 + 2 * Pubkey + u64 + u32
+        assert_eq!(params.try_to_vec().unwrap().len(), 8 + 4 + 64 + 8 + 4);
+    }
+}
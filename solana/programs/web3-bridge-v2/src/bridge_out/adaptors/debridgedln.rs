@@ -1,9 +1,15 @@
 use {
     crate::{
         bridge_out::{
+            check_accounts_len,
+            check_address,
+            check_pda,
+            check_writable,
+            AdaptorID,
+            BridgeAdaptor,
+            BridgeResult,
             BridgeTo,
             BridgeToArgs,
-            BridgeResult,
             SwapType,
         },
         common::{
@@ -25,6 +31,54 @@ use {
     },
 };
 
+pub struct DebridgedlnAdaptor;
+
+impl BridgeAdaptor for DebridgedlnAdaptor {
+    const ID: AdaptorID = AdaptorID::DEBRIDGEDLN;
+    const ACCOUNTS_LEN: usize = 7;
+    const CHAIN_REGISTRY_REQUIRED: bool = false;
+    type Args = BridgeToDebridgedlnArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToDebridgedlnArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        _data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        let accounts = ctx.remaining_accounts;
+        let debridge_program = debridgedln_program::id();
+        check_address(&accounts[0], &debridge_program)?;
+        // state, PDA: seeds = [b"STATE"]
+        check_pda(&accounts[1], &[b"STATE"], &debridge_program)?;
+        // give_order_state, PDA: seeds = [b"GIVE_ORDER_STATE", orderid]
+        check_pda(&accounts[2], &[b"GIVE_ORDER_STATE", &args.orderid], &debridge_program)?;
+        check_writable(&accounts[2])?;
+        // authorized_native_sender, PDA: seeds = [b"AUTHORIZED_NATIVE_SENDER", take.chain_id]
+        check_pda(&accounts[3], &[b"AUTHORIZED_NATIVE_SENDER", &args.order_args.take.chain_id], &debridge_program)?;
+        // give_order_wallet, PDA: seeds = [b"GIVE_ORDER_WALLET", orderid]
+        check_pda(&accounts[4], &[b"GIVE_ORDER_WALLET", &args.orderid], &debridge_program)?;
+        check_writable(&accounts[4])?;
+        // nonce_master, PDA: seeds = [b"NONCE", payer]
+        check_pda(&accounts[5], &[b"NONCE", ctx.accounts.payer.key().as_ref()], &debridge_program)?;
+        check_writable(&accounts[5])?;
+        // fee_ledger_wallet, PDA: seeds = [b"FEE_LEDGER_WALLET", mint]
+        check_pda(&accounts[6], &[b"FEE_LEDGER_WALLET", ctx.accounts.mint.key().as_ref()], &debridge_program)?;
+        check_writable(&accounts[6])
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
 pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: BridgeToArgs, bridge_to_debridgedln_args: BridgeToDebridgedlnArgs) -> Result<BridgeResult> {
 
     require!(data.swap_type == SwapType::BRIDGE, DebridgedlnErrorCode::DebridgeDoNotSupportSwapType);
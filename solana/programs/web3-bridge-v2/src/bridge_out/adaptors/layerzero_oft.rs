@@ -0,0 +1,352 @@
+use {
+    crate::{
+        bridge_out::{
+            check_accounts_len, check_address, check_pda, check_writable,
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs, OftRegistry,
+        },
+        common::{layerzero_endpoint_program as Endpoint, vec_to_hex_string, XBridgeErrorCode},
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{instruction::Instruction, program::invoke},
+    },
+    anchor_spl::token::{Mint, TokenAccount},
+    serde::Serialize,
+    tiny_keccak::{Hasher, Keccak},
+};
+
+// LayerZero endpoint id of solana mainnet
+pub const SOLANA_EID: u32 = 30168;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BridgeToOftArgs {
+    pub min_amount_ld: u64,     // min amount received on the destination, in local decimals
+    pub options: Vec<u8>,       // LayerZero executor options, e.g. destination gas
+    pub native_fee: u64,        // lamports paid to the endpoint, quoted by quote_send
+}
+
+impl BridgeToOftArgs {
+    pub fn try_from_vec(data: &[u8]) -> Result<BridgeToOftArgs> {
+        let decoded_args = BridgeToOftArgs::try_from_slice(&data)?;
+        Ok(decoded_args)
+    }
+}
+
+// SendParams of the oft program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OftSendParams {
+    pub dst_eid: u32,
+    pub to: [u8; 32],
+    pub amount_ld: u64,
+    pub min_amount_ld: u64,
+    pub options: Vec<u8>,
+    pub compose_msg: Option<Vec<u8>>,
+    pub native_fee: u64,
+    pub lz_token_fee: u64,
+}
+
+// GUID of the message from sender to receiver with the given nonce, as generated by the endpoint:
+// keccak256(nonce, src_eid, sender, dst_eid, receiver)
+pub fn message_guid(nonce: u64, sender: &Pubkey, dst_eid: u32, receiver: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut hash = [0u8; 32];
+    hasher.update(&nonce.to_be_bytes());
+    hasher.update(&SOLANA_EID.to_be_bytes());
+    hasher.update(sender.as_ref());
+    hasher.update(&dst_eid.to_be_bytes());
+    hasher.update(receiver);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+// PeerConfig.peer_address, after the account discriminator
+fn peer_address(peer: &AccountInfo) -> Result<[u8; 32]> {
+    let data = peer.try_borrow_data()?;
+    require!(data.len() >= 40, XBridgeErrorCode::InvalidAccount);
+    Ok(<[u8; 32]>::try_from(&data[8..40]).unwrap())
+}
+
+// Nonce.outbound_nonce, after the account discriminator and bump, the nonce of the last send
+fn outbound_nonce(nonce: &AccountInfo) -> Result<u64> {
+    let data = nonce.try_borrow_data()?;
+    require!(data.len() >= 17, XBridgeErrorCode::InvalidAccount);
+    Ok(u64::from_le_bytes(<[u8; 8]>::try_from(&data[9..17]).unwrap()))
+}
+
+#[derive(Accounts)]
+pub struct BridgeToOft<'info> {
+    #[account(mut)]
+    /// Payer.
+    /// Owner of the sent tokens and payer of the native fee.
+    pub payer: Signer<'info>,
+
+    /// OFT Registry
+    /// PDA: seeds = [b"oft", mint], set by set_oft.
+    /// CHECK: This account is a PDA account, which is used for layerzero oft.
+    pub oft_registry: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Peer Account
+    /// PDA: seeds = [b"Peer", oft_store, dst_eid], seeds::program = oft_program.
+    /// CHECK: This account is a PDA account, which is used for layerzero oft.
+    pub peer: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// OFT Store Account
+    /// PDA: seeds = [b"OFT", token_escrow], seeds::program = oft_program.
+    /// CHECK: This account is a PDA account, which is used for layerzero oft.
+    pub oft_store: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// User Token Account
+    /// ATA: mint = mint, owner = payer,
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    /// Token Escrow Account
+    /// oft_store.token_escrow, checked by the oft program.
+    /// CHECK: This account is used for layerzero oft.
+    pub token_escrow: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// tokenMint
+    /// give token mint
+    pub mint: Account<'info, Mint>,
+
+    /// Event Authority Account
+    /// PDA: seeds = [b"__event_authority"], seeds::program = oft_program.
+    /// CHECK: This account is a PDA account, which is used for layerzero oft.
+    pub event_authority: AccountInfo<'info>,
+
+    /// OFT Program
+    /// CHECK: oft_registry.oft_program
+    pub oft_program: AccountInfo<'info>,
+
+    /// SPL Token Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    /// CHECK: fixed
+    pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OftExtLog {
+    guid: String,
+    min_amount_ld: u64,
+    native_fee: u64,
+}
+
+/// to_chain_id is the LayerZero EID, mapped by chain_registry:
+/// 30101 => 1, 30102 => 56, 30106 => 43114, 30109 => 137, 30110 => 42161, 30111 => 10, 30184 => 8453.
+///
+/// remaining_accounts: oft_registry, oft_program, peer, oft_store, token_escrow, event_authority, then the endpoint send accounts
+/// (endpoint_program, oft_store, send_library_program, send_library_config, default_send_library_config,
+/// send_library_info, endpoint, nonce, event_authority, endpoint_program, send library accounts...)
+/// passed through to the oft program.
+pub struct OftAdaptor;
+
+impl BridgeAdaptor for OftAdaptor {
+    const ID: AdaptorID = AdaptorID::LAYERZERO_OFT;
+    const ACCOUNTS_LEN: usize = 16;
+    // the oft program removes dust below the shared decimals before debiting
+    const MEASURE_AMOUNT: bool = true;
+    type Args = BridgeToOftArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToOftArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        require!(args.min_amount_ld <= data.amount, XBridgeErrorCode::InvalidMinAmount);
+        require!(data.to.len() == 32, XBridgeErrorCode::InvalidRecipientLength);
+        let accounts = ctx.remaining_accounts;
+        let mint = ctx.accounts.mint.key();
+        // oft_registry, PDA: seeds = [b"oft", mint], web3_bridge_v2
+        check_pda(&accounts[0], &[b"oft", mint.as_ref()], &crate::ID)?;
+        require_keys_eq!(*accounts[0].owner, crate::ID, XBridgeErrorCode::InvalidAccount);
+        let registry = OftRegistry::try_deserialize(&mut &accounts[0].try_borrow_data()?[..])?;
+        require!(registry.enabled, XBridgeErrorCode::OftNotEnabled);
+
+        let oft_program = registry.oft_program;
+        let endpoint = Endpoint::id();
+        let dst_eid = u32::try_from(data.to_chain_id)
+            .map_err(|_| XBridgeErrorCode::InvalidToChainId)?
+            .to_be_bytes();
+        check_address(&accounts[1], &oft_program)?;
+        // peer, PDA: seeds = [b"Peer", oft_store, dst_eid], oft_program
+        check_pda(&accounts[2], &[b"Peer", accounts[3].key.as_ref(), &dst_eid], &oft_program)?;
+        check_writable(&accounts[2])?;
+        // oft_store, PDA: seeds = [b"OFT", token_escrow], oft_program
+        check_pda(&accounts[3], &[b"OFT", accounts[4].key.as_ref()], &oft_program)?;
+        require_keys_eq!(*accounts[3].owner, oft_program, XBridgeErrorCode::InvalidAccount);
+        check_writable(&accounts[3])?;
+        check_writable(&accounts[4])?;
+        // event_authority, PDA: seeds = [b"__event_authority"], oft_program
+        check_pda(&accounts[5], &[b"__event_authority"], &oft_program)?;
+        check_address(&accounts[6], &endpoint)?;
+        // sender of the endpoint send is the oft_store
+        check_address(&accounts[7], accounts[3].key)?;
+        // send_library_config, PDA: seeds = [b"SendLibraryConfig", oft_store, dst_eid], endpoint
+        check_pda(&accounts[9], &[b"SendLibraryConfig", accounts[3].key.as_ref(), &dst_eid], &endpoint)?;
+        // default_send_library_config, PDA: seeds = [b"SendLibraryConfig", dst_eid], endpoint
+        check_pda(&accounts[10], &[b"SendLibraryConfig", &dst_eid], &endpoint)?;
+        // endpoint, PDA: seeds = [b"Endpoint"], endpoint
+        check_pda(&accounts[12], &[b"Endpoint"], &endpoint)?;
+        // nonce, PDA: seeds = [b"Nonce", oft_store, dst_eid, peer_address], endpoint
+        let peer_address = peer_address(&accounts[2])?;
+        check_pda(&accounts[13], &[b"Nonce", accounts[3].key.as_ref(), &dst_eid, &peer_address], &endpoint)?;
+        check_writable(&accounts[13])?;
+        // event_authority, PDA: seeds = [b"__event_authority"], endpoint
+        check_pda(&accounts[14], &[b"__event_authority"], &endpoint)?;
+        check_address(&accounts[15], &endpoint)
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    data: BridgeToArgs,
+    bridge_to_oft_args: BridgeToOftArgs,
+) -> Result<BridgeResult> {
+    let params = OftSendParams {
+        dst_eid: data.to_chain_id as u32,
+        to: <[u8; 32]>::try_from(data.to.as_slice())
+            .map_err(|_| XBridgeErrorCode::InvalidRecipientLength)?,
+        amount_ld: data.amount,
+        min_amount_ld: bridge_to_oft_args.min_amount_ld,
+        options: bridge_to_oft_args.options,
+        compose_msg: None,
+        native_fee: bridge_to_oft_args.native_fee,
+        lz_token_fee: 0,
+    };
+
+    // send
+    let mut new_data = vec![102u8, 251u8, 20u8, 187u8, 65u8, 75u8, 12u8, 69u8];
+    new_data.extend_from_slice(&params.try_to_vec()?);
+
+    let bridge_to_oft = BridgeToOft {
+        // account in bridge_to.accounts
+        payer: ctx.accounts.payer.clone(),
+        mint: ctx.accounts.mint.clone(),
+        user_token_account: ctx.accounts.user_token_account.clone(),
+        token_program: ctx.accounts.token_program.clone(),
+        // account in bridge_to.remaining_accounts
+        oft_registry: ctx.remaining_accounts[0].to_account_info(),
+        oft_program: ctx.remaining_accounts[1].to_account_info(),
+        peer: ctx.remaining_accounts[2].to_account_info(),
+        oft_store: ctx.remaining_accounts[3].to_account_info(),
+        token_escrow: ctx.remaining_accounts[4].to_account_info(),
+        event_authority: ctx.remaining_accounts[5].to_account_info(),
+    };
+
+    // send is an event_cpi instruction, event_authority and program follow its accounts
+    let mut account_metas = vec![
+        AccountMeta::new(bridge_to_oft.payer.key(), true),
+        AccountMeta::new(bridge_to_oft.peer.key(), false),
+        AccountMeta::new(bridge_to_oft.oft_store.key(), false),
+        AccountMeta::new(bridge_to_oft.user_token_account.key(), false),
+        AccountMeta::new(bridge_to_oft.token_escrow.key(), false),
+        AccountMeta::new(bridge_to_oft.mint.key(), false),
+        AccountMeta::new_readonly(bridge_to_oft.token_program.key(), false),
+        AccountMeta::new_readonly(bridge_to_oft.event_authority.key(), false),
+        AccountMeta::new_readonly(bridge_to_oft.oft_program.key(), false),
+    ];
+    let mut account_infos = bridge_to_oft.to_account_infos();
+    // the endpoint send accounts are passed through as given
+    for account in &ctx.remaining_accounts[6..] {
+        account_metas.push(AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        });
+        account_infos.push(account.to_account_info());
+    }
+
+    let ix = Instruction {
+        program_id: bridge_to_oft.oft_program.key(),
+        data: new_data,
+        accounts: account_metas,
+    };
+
+    invoke(
+        &ix,
+        &account_infos
+    )?;
+
+    // the endpoint has incremented the outbound nonce for this send
+    let dst_eid = params.dst_eid;
+    let receiver = peer_address(&bridge_to_oft.peer)?;
+    let guid = message_guid(outbound_nonce(&ctx.remaining_accounts[13])?, bridge_to_oft.oft_store.key, dst_eid, &receiver);
+    let oft_log = OftExtLog {
+        guid: "0x".to_string() + &vec_to_hex_string(guid.to_vec()),
+        min_amount_ld: bridge_to_oft_args.min_amount_ld,
+        native_fee: bridge_to_oft_args.native_fee,
+    };
+    Ok(BridgeResult{
+        ext: serde_json::to_string(&oft_log).unwrap(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::solana_program::hash as hash;
+
+    #[test]
+    fn sighash() {
+        let mut sighash = [0u8; 8];
+        sighash.copy_from_slice(&hash::hash(b"global:send").to_bytes()[..8]);
+        assert_eq!(sighash, [102u8, 251u8, 20u8, 187u8, 65u8, 75u8, 12u8, 69u8]);
+    }
+
+    #[test]
+    fn encode_send_params() {
+        let params = OftSendParams {
+            dst_eid: 30101,
+            to: [0; 32],
+            amount_ld: 1_000_000,
+            min_amount_ld: 990_000,
+            options: vec![0, 3],
+            compose_msg: None,
+            native_fee: 5000,
+            lz_token_fee: 0,
+        };
+        // u32 + [u8; 32] + 2 * u64 + (u32 + 2) + None + 2 * u64
+        assert_eq!(params.try_to_vec().unwrap().len(), 4 + 32 + 16 + 6 + 1 + 16);
+    }
+
+    #[test]
+    fn guid_depends_on_nonce() {
+        let sender = Pubkey::new_unique();
+        let receiver = [1u8; 32];
+        assert_ne!(message_guid(1, &sender, 30101, &receiver), message_guid(2, &sender, 30101, &receiver));
+    }
+
+    #[test]
+    fn guid_accounts_too_short() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![0u8; 17];
+        data[9..17].copy_from_slice(&7u64.to_le_bytes());
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert_eq!(outbound_nonce(&account).unwrap(), 7);
+        assert!(peer_address(&account).is_err());
+
+        let mut lamports = 0;
+        let mut data = vec![0u8; 16];
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert!(outbound_nonce(&account).is_err());
+    }
+}
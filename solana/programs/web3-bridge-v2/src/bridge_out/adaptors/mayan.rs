@@ -0,0 +1,269 @@
+use {
+    crate::{
+        bridge_out::{check_address, check_writable, BridgeTo},
+        common::{vec_to_hex_string, XBridgeErrorCode},
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{instruction::Instruction, program::invoke},
+    },
+    anchor_spl::{
+        associated_token::{self, get_associated_token_address, Create},
+        token::{self, Mint, TokenAccount, Transfer},
+    },
+    serde::Serialize,
+};
+
+/// Wormhole chain id of to_chain_id.
+/// A Mayan route reaches the chains enabled in chain_registry under its own adaptor id.
+pub fn mayan_destination_chain(to_chain_id: u64) -> Result<u16> {
+    u16::try_from(to_chain_id).map_err(|_| XBridgeErrorCode::InvalidToChainId.into())
+}
+
+/// Checks the state / ledger token account shared by the Mayan adaptors.
+pub fn check_mayan_accounts<'info>(
+    ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    state: &AccountInfo,
+    state_account: &AccountInfo,
+) -> Result<()> {
+    check_writable(state)?;
+    // state_account, ATA: mint = mint, owner = state
+    check_address(
+        state_account,
+        &get_associated_token_address(state.key, &ctx.accounts.mint.key()),
+    )?;
+    check_writable(state_account)
+}
+
+/// Moves the bridged amount into the token account of the Mayan state, created if missing.
+pub fn lock_to_state<'info>(
+    ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    state: &AccountInfo<'info>,
+    state_account: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    associated_token::create_idempotent(
+        CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: state_account.clone(),
+                authority: state.clone(),
+                mint: ctx.accounts.mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        )
+    )?;
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: state_account.clone(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+#[derive(Accounts)]
+pub struct BridgeToMayan<'info> {
+    #[account(mut)]
+    /// Trader.
+    /// This account is used to pay for the bridge token.
+    pub trader: Signer<'info>,
+
+    #[account(mut)]
+    /// State / Ledger Account
+    /// PDA of the Mayan program, holds the locked tokens until a Mayan relayer picks up the order.
+    /// CHECK: This account is a PDA account, which is used for mayan.
+    pub state: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// State Token Account
+    /// ATA: mint = mint, owner = state,
+    /// CHECK: This Account is used to receive token for mayan,
+    pub state_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Trader Token Account
+    /// ATA: mint = mint, owner = trader,
+    pub trader_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Mint (read-only).
+    pub mint: Account<'info, Mint>,
+
+    /// SPL Token Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    /// CHECK: fixed
+    pub token_program: AccountInfo<'info>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: AccountInfo<'info>,
+
+    /// Mayan Program
+    /// CHECK: checked by the adaptor
+    pub mayan_program: AccountInfo<'info>,
+}
+
+impl<'info> BridgeToMayan<'info> {
+    /// remaining_accounts: state, state_account, mayan_program, followed by the route's own accounts.
+    pub fn from_ctx(ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>) -> Self {
+        BridgeToMayan {
+            trader: ctx.accounts.payer.clone(),
+            state: ctx.remaining_accounts[0].to_account_info(),
+            state_account: ctx.remaining_accounts[1].to_account_info(),
+            trader_account: ctx.accounts.user_token_account.clone(),
+            mint: ctx.accounts.mint.clone(),
+            token_program: ctx.accounts.token_program.clone(),
+            system_program: ctx.accounts.system_program.clone(),
+            mayan_program: ctx.remaining_accounts[2].to_account_info(),
+        }
+    }
+
+    pub fn keys(&self) -> MayanOrderKeys {
+        MayanOrderKeys {
+            trader: self.trader.key(),
+            state: self.state.key(),
+            state_account: self.state_account.key(),
+            trader_account: self.trader_account.key(),
+            mint: self.mint.key(),
+        }
+    }
+
+    /// Locks amount into the state token account and invokes the Mayan program with data and the route's metas,
+    /// route_accounts are the route's own accounts, checked by its validate_accounts.
+    pub fn lock_and_invoke(
+        &self,
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        amount: u64,
+        data: Vec<u8>,
+        accounts: Vec<AccountMeta>,
+        route_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        lock_to_state(ctx, &self.state, &self.state_account, amount)?;
+
+        let ix = Instruction {
+            program_id: self.mayan_program.key(),
+            data,
+            accounts,
+        };
+
+        let mut account_infos = self.to_account_infos();
+        account_infos.extend_from_slice(route_accounts);
+        invoke(
+            &ix,
+            &account_infos
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Keys of a Mayan order, each Mayan program takes them in the order of its IDL.
+#[derive(Debug, Clone)]
+pub struct MayanOrderKeys {
+    pub trader: Pubkey,
+    pub state: Pubkey,
+    pub state_account: Pubkey,
+    pub trader_account: Pubkey,
+    pub mint: Pubkey,
+}
+
+// gas drop and referrer echoed in LogBridgeToVersion1.ext
+#[derive(Debug, Clone, Serialize)]
+pub struct MayanExtLog {
+    pub gas_drop: u64,
+    pub ref_address: String,
+    pub fee_rate_ref: u8,
+}
+
+impl MayanExtLog {
+    pub fn from_args(gas_drop: u64, ref_address: [u8; 32], fee_rate_ref: u8) -> Self {
+        MayanExtLog {
+            gas_drop,
+            ref_address: "0x".to_string() + &vec_to_hex_string(ref_address.to_vec()),
+            fee_rate_ref,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {
+        super::*,
+        crate::{
+            bridge_out::{
+                mayan_mctp::init_bridge_ledger_metas, mayan_swift::init_order_metas,
+                mayan_wh_swap::init_swap_order_metas,
+            },
+            common::mayan_fee_manager_program as MayanFeeManagerProgram,
+        },
+        anchor_lang::system_program,
+        anchor_spl::token::Token,
+    };
+
+    #[test]
+    fn destination_chain() {
+        assert_eq!(mayan_destination_chain(30).unwrap(), 30);
+        assert!(mayan_destination_chain(u16::MAX as u64 + 1).is_err());
+    }
+
+    #[test]
+    fn ext_log() {
+        let ext = serde_json::to_string(&MayanExtLog::from_args(10, [0xab; 32], 12)).unwrap();
+        assert_eq!(
+            ext,
+            format!(r#"{{"gas_drop":10,"ref_address":"0x{}","fee_rate_ref":12}}"#, "ab".repeat(32))
+        );
+    }
+
+    #[test]
+    fn account_order() {
+        let keys = MayanOrderKeys {
+            trader: Pubkey::new_unique(),
+            state: Pubkey::new_unique(),
+            state_account: Pubkey::new_unique(),
+            trader_account: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+        };
+        // mctp init_bridge_ledger and wh swap init_swap_order share the same accounts
+        let ledger_order = vec![
+            (keys.trader, true, true),
+            (keys.state, false, true),
+            (keys.state_account, false, false),
+            (keys.mint, false, false),
+            (system_program::ID, false, false),
+        ];
+        let routes = [
+            (
+                "swift init_order",
+                init_order_metas(&keys),
+                vec![
+                    (keys.trader, true, true),
+                    (keys.trader, true, true),
+                    (keys.state, false, true),
+                    (keys.state_account, false, true),
+                    (keys.trader_account, false, true),
+                    (keys.mint, false, false),
+                    (MayanFeeManagerProgram::id(), false, false),
+                    (Token::id(), false, false),
+                    (system_program::ID, false, false),
+                ],
+            ),
+            ("mctp init_bridge_ledger", init_bridge_ledger_metas(&keys), ledger_order.clone()),
+            ("wh swap init_swap_order", init_swap_order_metas(&keys), ledger_order),
+        ];
+        for (route, metas, expected) in routes {
+            assert_eq!(metas.len(), expected.len(), "{}", route);
+            for (meta, (pubkey, is_signer, is_writable)) in metas.iter().zip(expected) {
+                assert_eq!((meta.pubkey, meta.is_signer, meta.is_writable), (pubkey, is_signer, is_writable), "{}", route);
+            }
+        }
+    }
+}
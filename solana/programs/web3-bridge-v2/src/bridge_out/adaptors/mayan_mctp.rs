@@ -0,0 +1,170 @@
+use {
+    crate::{
+        bridge_out::{
+            check_accounts_len, check_address, check_pda,
+            mayan::{check_mayan_accounts, mayan_destination_chain, BridgeToMayan, MayanExtLog, MayanOrderKeys},
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs,
+        },
+        common::{mayan_mctp_program as MayanMctpProgram, safe_to_fixed_bytes, XBridgeErrorCode},
+    },
+    anchor_lang::{prelude::*, system_program},
+};
+
+/// Mayan MCTP, a CCTP burn with gas drop picked up by Mayan relayers from the bridge ledger.
+/// to_chain_id is the wormhole chain id, enabled per chain in chain_registry under this adaptor id.
+pub struct MayanMctpAdaptor;
+
+impl BridgeAdaptor for MayanMctpAdaptor {
+    const ID: AdaptorID = AdaptorID::MAYAN_MCTP;
+    const ACCOUNTS_LEN: usize = 3;
+    type Args = BridgeToMayanMctpArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToMayanMctpArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        mayan_destination_chain(data.to_chain_id)?;
+        require!(args.fee_redeem < data.amount, XBridgeErrorCode::InvalidRelayerFee);
+        let accounts = ctx.remaining_accounts;
+        // ledger, PDA: seeds = [b"LEDGER", payer, random_key], mctp_program
+        check_pda(
+            &accounts[0],
+            &[b"LEDGER", ctx.accounts.payer.key().as_ref(), &args.random_key],
+            &MayanMctpProgram::id()
+        )?;
+        check_address(&accounts[2], &MayanMctpProgram::id())?;
+        check_mayan_accounts(ctx, &accounts[0], &accounts[1])
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    data: BridgeToArgs,
+    bridge_to_mayan_mctp_args: BridgeToMayanMctpArgs,
+) -> Result<BridgeResult> {
+    let params = MayanMctpParams {
+        amount_in: data.amount,
+        dest_address: safe_to_fixed_bytes::<32>(data.to)?,
+        destination_chain: mayan_destination_chain(data.to_chain_id)?,
+        fee_redeem: bridge_to_mayan_mctp_args.fee_redeem,
+        gas_drop: bridge_to_mayan_mctp_args.gas_drop,
+        ref_address: bridge_to_mayan_mctp_args.ref_address,
+        fee_rate_ref: bridge_to_mayan_mctp_args.fee_rate_ref,
+        random_key: bridge_to_mayan_mctp_args.random_key,
+    };
+
+    let bridge_to_mayan = BridgeToMayan::from_ctx(&ctx);
+    bridge_to_mayan.lock_and_invoke(&ctx, data.amount, params.encode()?, init_bridge_ledger_metas(&bridge_to_mayan.keys()), &[])?;
+
+    let mayan_log = MayanExtLog::from_args(
+        bridge_to_mayan_mctp_args.gas_drop,
+        bridge_to_mayan_mctp_args.ref_address,
+        bridge_to_mayan_mctp_args.fee_rate_ref,
+    );
+    Ok(BridgeResult{
+        ext: serde_json::to_string(&mayan_log).unwrap(),
+    })
+}
+
+/// init_bridge_ledger accounts of the mctp program, the ledger takes the locked tokens from its token account.
+pub fn init_bridge_ledger_metas(keys: &MayanOrderKeys) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(keys.trader, true),
+        AccountMeta::new(keys.state, false),
+        AccountMeta::new_readonly(keys.state_account, false),
+        AccountMeta::new_readonly(keys.mint, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ]
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BridgeToMayanMctpArgs {
+    pub fee_redeem: u64,        // paid to the relayer redeeming on the destination, in the bridged token
+    pub gas_drop: u64,          // native gas delivered to the recipient on the destination
+    pub ref_address: [u8; 32],  // referrer receiving fee_rate_ref of the amount
+    pub fee_rate_ref: u8,       // referrer fee, in bps
+    pub random_key: [u8; 32],   // seed of the ledger PDA
+}
+
+impl BridgeToMayanMctpArgs {
+    pub fn try_from_vec(data: &[u8]) -> Result<BridgeToMayanMctpArgs> {
+        let decoded_args = BridgeToMayanMctpArgs::try_from_slice(&data)?;
+        Ok(decoded_args)
+    }
+}
+
+// init_bridge_ledger params of the mctp program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MayanMctpParams {
+    pub amount_in: u64,
+    pub dest_address: [u8; 32],
+    pub destination_chain: u16,
+    pub fee_redeem: u64,
+    pub gas_drop: u64,
+    pub ref_address: [u8; 32],
+    pub fee_rate_ref: u8,
+    pub random_key: [u8; 32],
+}
+
+impl MayanMctpParams {
+    pub const INSTRUCTION_BYTES: [u8; 8] = [167, 102, 128, 106, 121, 118, 186, 76];
+
+    // init_bridge_ledger instruction data, the sighash then the borsh params
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut data = Self::INSTRUCTION_BYTES.to_vec();
+        data.extend_from_slice(&self.try_to_vec()?);
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::solana_program::hash as hash;
+
+    #[test]
+    fn sighash() {
+        let mut sighash = [0u8; 8];
+        sighash.copy_from_slice(&hash::hash(b"global:init_bridge_ledger").to_bytes()[..8]);
+        assert_eq!(sighash, MayanMctpParams::INSTRUCTION_BYTES);
+    }
+
+    #[test]
+    fn encode_params() {
+        let params = MayanMctpParams {
+            amount_in: 1,
+            dest_address: [2; 32],
+            destination_chain: 30,
+            fee_redeem: 3,
+            gas_drop: 4,
+            ref_address: [5; 32],
+            fee_rate_ref: 6,
+            random_key: [7; 32],
+        };
+        let data = params.encode().unwrap();
+        assert_eq!(data.len(), 131);
+        assert_eq!(data[..8], MayanMctpParams::INSTRUCTION_BYTES);
+        assert_eq!(data[8..16], 1u64.to_le_bytes());
+        assert_eq!(data[16..48], [2; 32]);
+        assert_eq!(data[48..50], 30u16.to_le_bytes());
+        assert_eq!(data[50..58], 3u64.to_le_bytes());
+        assert_eq!(data[58..66], 4u64.to_le_bytes());
+        assert_eq!(data[66..98], [5; 32]);
+        assert_eq!(data[98], 6);
+        assert_eq!(data[99..], [7; 32]);
+    }
+}
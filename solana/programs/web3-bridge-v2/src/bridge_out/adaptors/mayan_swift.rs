@@ -1,21 +1,54 @@
 use {
     crate::{
-        bridge_out::{BridgeResult, BridgeTo, BridgeToArgs},
+        bridge_out::{
+            check_accounts_len, check_address, check_pda,
+            mayan::{check_mayan_accounts, mayan_destination_chain, BridgeToMayan, MayanExtLog, MayanOrderKeys},
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs,
+        },
         common::{
             mayan_fee_manager_program as MayanFeeManagerProgram,
-            mayan_swift_program as MayanSwiftProgram, safe_to_fixed_bytes, safe_to_u16,
+            mayan_swift_program as MayanSwiftProgram, safe_to_fixed_bytes,
         },
     },
-    anchor_lang::{
-        prelude::*,
-        solana_program::{instruction::Instruction, program::invoke},
-    },
-    anchor_spl::{
-        associated_token::{self, Create},
-        token::{self, Mint, Token, TokenAccount, Transfer},
-    },
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::token::Token,
 };
 
+pub struct MayanSwiftAdaptor;
+
+impl BridgeAdaptor for MayanSwiftAdaptor {
+    const ID: AdaptorID = AdaptorID::MAYAN_SWIFT;
+    const ACCOUNTS_LEN: usize = 4;
+    type Args = BridgeToMayanSwiftArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToMayanSwiftArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        mayan_destination_chain(data.to_chain_id)?;
+        let accounts = ctx.remaining_accounts;
+        // state, PDA: seeds = [b"STATE_SOURCE", order_hash], swift_program
+        check_pda(&accounts[0], &[b"STATE_SOURCE", &args.order_hash], &MayanSwiftProgram::id())?;
+        check_address(&accounts[2], &MayanSwiftProgram::id())?;
+        check_address(&accounts[3], &MayanFeeManagerProgram::id())?;
+        check_mayan_accounts(ctx, &accounts[0], &accounts[1])
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
     data: BridgeToArgs,
@@ -23,93 +56,60 @@ pub fn handler<'info>(
 ) -> Result<BridgeResult> {
 
     // msg!("order_hash: {}", vec_to_hex_string(bridge_to_mayan_args.order_hash.to_vec()));
-    associated_token::create_idempotent(
-        CpiContext::new(
-            ctx.accounts.associated_token_program.to_account_info(),
-            Create {
-                payer: ctx.accounts.payer.to_account_info(),
-                associated_token: ctx.remaining_accounts[1].to_account_info(),
-                authority: ctx.remaining_accounts[0].to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                token_program: ctx.accounts.token_program.to_account_info(),
-            },
-        )
-    )?;
-    
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.user_token_account.to_account_info(),
-                to: ctx.remaining_accounts[1].to_account_info(),
-                authority: ctx.accounts.payer.to_account_info(),
-            },
-        ),
-        data.amount,
-    )?;
-    
-
     let mayan_swift_args = MayanSwiftArgs {
         instruction: MayanSwiftArgs::INSTRUCTION_BYTES,
         amount_in_min: data.amount,
         native_input: bridge_to_mayan_args.native_input as u8,
         fee_submit: bridge_to_mayan_args.fee_submit,
         dest_address: safe_to_fixed_bytes::<32>(data.to)?,
-        destination_chain: safe_to_u16(data.to_chain_id)?,
+        destination_chain: mayan_destination_chain(data.to_chain_id)?,
         token_out: bridge_to_mayan_args.token_out,
         amount_out_min: bridge_to_mayan_args.amount_out_min,
-        gas_drop: 0,
+        gas_drop: bridge_to_mayan_args.gas_drop,
         fee_cancel: bridge_to_mayan_args.fee_cancel,
         fee_refund: bridge_to_mayan_args.fee_refund,
         deadline: bridge_to_mayan_args.deadline,
-        ref_address: [0; 32],
-        fee_rate_ref: 0,
+        ref_address: bridge_to_mayan_args.ref_address,
+        fee_rate_ref: bridge_to_mayan_args.fee_rate_ref,
         fee_rate_mayan: bridge_to_mayan_args.fee_rate_mayan,
         auction_mode: bridge_to_mayan_args.auction_mode,
         random_key: bridge_to_mayan_args.random_key,
     };
-    let mayan_swift_data_bytes = MayanSwiftArgs::encode(&mayan_swift_args);
 
-    let bridge_to_mayan = BridgeToMayan {
-        trader: ctx.accounts.payer.clone(),
-        relayer: ctx.accounts.payer.clone(),
-        state: ctx.remaining_accounts[0].to_account_info(),
-        state_account: ctx.remaining_accounts[1].to_account_info(),
-        relayer_account: ctx.accounts.user_token_account.clone(),
-        mint: ctx.accounts.mint.clone(),
-        fee_manager_program: ctx.remaining_accounts[3].to_account_info(),
-        token_program: ctx.accounts.token_program.clone(),
-        system_program: ctx.accounts.system_program.clone(),
-        swift_program: ctx.remaining_accounts[2].to_account_info(),
-    };
-
-    let ix = Instruction {
-        program_id: MayanSwiftProgram::id(),
-        data: mayan_swift_data_bytes,
-        accounts: vec![
-            AccountMeta::new(bridge_to_mayan.trader.key(), true),
-            AccountMeta::new(bridge_to_mayan.relayer.key(), true),
-            AccountMeta::new(bridge_to_mayan.state.key(), false),
-            AccountMeta::new(bridge_to_mayan.state_account.key(), false),
-            AccountMeta::new(bridge_to_mayan.relayer_account.key(), false),
-            AccountMeta::new_readonly(bridge_to_mayan.mint.key(), false),
-            AccountMeta::new_readonly(bridge_to_mayan.fee_manager_program.key(), false),
-            AccountMeta::new_readonly(bridge_to_mayan.token_program.key(), false),
-            AccountMeta::new_readonly(bridge_to_mayan.system_program.key(), false),
-        ],
-    };
-
-    invoke(
-        &ix, 
-        &bridge_to_mayan.to_account_infos()
+    let bridge_to_mayan = BridgeToMayan::from_ctx(&ctx);
+    bridge_to_mayan.lock_and_invoke(
+        &ctx,
+        data.amount,
+        MayanSwiftArgs::encode(&mayan_swift_args),
+        init_order_metas(&bridge_to_mayan.keys()),
+        &ctx.remaining_accounts[3..MayanSwiftAdaptor::ACCOUNTS_LEN],
     )?;
 
-    Ok(BridgeResult {
-        ext: "".to_string(),
+    let mayan_log = MayanExtLog::from_args(
+        bridge_to_mayan_args.gas_drop,
+        bridge_to_mayan_args.ref_address,
+        bridge_to_mayan_args.fee_rate_ref,
+    );
+    Ok(BridgeResult{
+        ext: serde_json::to_string(&mayan_log).unwrap(),
     })
 }
 
+/// init_order accounts of the swift program, the trader relays its own order.
+pub fn init_order_metas(keys: &MayanOrderKeys) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(keys.trader, true),
+        AccountMeta::new(keys.trader, true),
+        AccountMeta::new(keys.state, false),
+        AccountMeta::new(keys.state_account, false),
+        AccountMeta::new(keys.trader_account, false),
+        AccountMeta::new_readonly(keys.mint, false),
+        AccountMeta::new_readonly(MayanFeeManagerProgram::id(), false),
+        AccountMeta::new_readonly(Token::id(), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ]
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct BridgeToMayanSwiftArgs {
     pub native_input: bool,
@@ -123,10 +123,23 @@ pub struct BridgeToMayanSwiftArgs {
     pub auction_mode: u8,
     pub random_key: [u8; 32],
     pub order_hash: [u8; 32],
+    pub gas_drop: u64,          // native gas delivered to the recipient on the destination
+    pub ref_address: [u8; 32],  // referrer receiving fee_rate_ref of the amount, paid by mayan
+    pub fee_rate_ref: u8,       // referrer fee, in bps
 }
 
 impl BridgeToMayanSwiftArgs {
+    // args without gas_drop, ref_address and fee_rate_ref
+    pub const LEGACY_LEN: usize = 139;
+    pub const LEN: usize = Self::LEGACY_LEN + 8 + 32 + 1;
+
     pub fn try_from_vec(data: &[u8]) -> Result<BridgeToMayanSwiftArgs> {
+        // compatible with current version with no gas drop and referrer
+        if data.len() == Self::LEGACY_LEN {
+            let mut padded = data.to_vec();
+            padded.resize(Self::LEN, 0);
+            return Ok(BridgeToMayanSwiftArgs::try_from_slice(&padded)?);
+        }
         let decoded_args: BridgeToMayanSwiftArgs = BridgeToMayanSwiftArgs::try_from_slice(&data)?;
         Ok(decoded_args)
     }
@@ -157,7 +170,7 @@ impl MayanSwiftArgs {
     pub const INSTRUCTION_BYTES: [u8; 8] = [32, 76, 41, 12, 39, 162, 132, 219];
     
     pub fn encode(&self) -> Vec<u8> {
-        let mut data = Vec::with_capacity(239); // Total size = 239 bytes
+        let mut data = Vec::with_capacity(198); // Total size = 198 bytes
 
         data.extend_from_slice(&self.instruction);
         data.extend_from_slice(&self.amount_in_min.to_le_bytes());
@@ -214,74 +227,66 @@ impl MayanSwiftArgs {
     }
 }
 
-#[derive(Accounts)]
-#[instruction(bridge_to_mayan_args: BridgeToMayanSwiftArgs, bridge_to_args: BridgeToArgs)]
-pub struct BridgeToMayan<'info> {
-    #[account(mut)]
-    /// Trader.
-    /// This account is used to pay for the bridge token.
-    /// CHECK: This account is used to pay for the bridge token.
-    pub trader: Signer<'info>,
-
-    #[account(mut)]
-    /// Relayer.
-    /// This account is used to pay for the transaction as payer.
-    /// CHECK: This account is used to pay for the transaction as payer.
-    pub relayer: Signer<'info>,
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    #[account(
-        mut,
-        seeds = [b"STATE_SOURCE".as_ref(), &bridge_to_mayan_args.order_hash],
-        bump,
-        seeds::program = MayanSwiftProgram::id(),
-    )]
-    /// CHECK: This account is used to create ATA account to receive token for mayan.
-    pub state: AccountInfo<'info>,
-
-    #[account(
-        mut,
-        // associated_token::mint = mint,
-        // associated_token::authority = state,
-    )]
-    /// State Token Account
-    /// ATA: mint = mint, owner = relayer,
-    /// CHECK: This Account is used to receive token for mayan,
-    pub state_account: AccountInfo<'info>,
-
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = relayer,
-    )]
-    /// User Token Account
-    /// ATA: mint = mint, owner = payer,
-    /// CHECK: This Account is used to send token for user,
-    pub relayer_account: Account<'info, TokenAccount>,
-
-    /// CHECK: Mint (read-only).
-    pub mint: Account<'info, Mint>,
-
-    /// Mayan Fee Manager Program
-    /// "5VtQHnhs2pfVEr68qQsbTRwKh4JV5GTu9mBHgHFxpHeQ"
-    /// CHECK: Mayan Fee Manager Program ID
-    #[account(address = MayanFeeManagerProgram::id())]
-    pub fee_manager_program: AccountInfo<'info>,
+    fn swift_args() -> BridgeToMayanSwiftArgs {
+        BridgeToMayanSwiftArgs {
+            native_input: false,
+            fee_submit: 1,
+            token_out: [2; 32],
+            amount_out_min: 3,
+            fee_cancel: 4,
+            fee_refund: 5,
+            deadline: 6,
+            fee_rate_mayan: 7,
+            auction_mode: 2,
+            random_key: [8; 32],
+            order_hash: [9; 32],
+            gas_drop: 10,
+            ref_address: [11; 32],
+            fee_rate_ref: 12,
+        }
+    }
 
-    /// SPL Token Program
-    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-    /// CHECK: fixed
-    #[account(address = Token::id())]
-    pub token_program: AccountInfo<'info>,
+    #[test]
+    fn decode_args() {
+        let data = swift_args().try_to_vec().unwrap();
+        assert_eq!(data.len(), BridgeToMayanSwiftArgs::LEN);
+        let decoded = BridgeToMayanSwiftArgs::try_from_vec(&data).unwrap();
+        assert_eq!(decoded.gas_drop, 10);
+        assert_eq!(decoded.ref_address, [11; 32]);
+        assert_eq!(decoded.fee_rate_ref, 12);
+    }
 
-    /// System Program.
-    /// "11111111111111111111111111111111"
-    /// CHECK: fixed
-    #[account(address = System::id())]
-    pub system_program: AccountInfo<'info>,
+    #[test]
+    fn decode_legacy_args() {
+        let data = swift_args().try_to_vec().unwrap();
+        let decoded = BridgeToMayanSwiftArgs::try_from_vec(&data[..BridgeToMayanSwiftArgs::LEGACY_LEN]).unwrap();
+        assert_eq!(decoded.order_hash, [9; 32]);
+        assert_eq!(decoded.gas_drop, 0);
+        assert_eq!(decoded.ref_address, [0; 32]);
+        assert_eq!(decoded.fee_rate_ref, 0);
+    }
 
-    /// Mayan Swift Program
-    /// "BLZRi6frs4X4DNLw56V4EXai1b6QVESN1BhHBTYM9VcY"
-    /// CHECK: Mayan Swift Program ID
-    #[account(address = MayanSwiftProgram::id())]
-    pub swift_program: AccountInfo<'info>,
+    #[test]
+    fn encode_args() {
+        let mut args = MayanSwiftArgs::new(1, 1, 2, [3; 32], 30, [4; 32], 5, 6, 2, [7; 32]);
+        args.gas_drop = 8;
+        args.ref_address = [9; 32];
+        args.fee_rate_ref = 10;
+        let data = args.encode();
+        assert_eq!(data.len(), 198);
+        assert_eq!(data[..8], MayanSwiftArgs::INSTRUCTION_BYTES);
+        assert_eq!(data[8..16], 1u64.to_le_bytes());
+        assert_eq!(data[16], 1);
+        assert_eq!(data[25..57], [3; 32]);
+        assert_eq!(data[57..59], 30u16.to_le_bytes());
+        assert_eq!(data[59..91], [4; 32]);
+        assert_eq!(data[99..107], 8u64.to_le_bytes());
+        assert_eq!(data[131..163], [9; 32]);
+        assert_eq!(data[163..166], [10, 6, 2]);
+        assert_eq!(data[166..], [7; 32]);
+    }
 }
@@ -0,0 +1,191 @@
+use {
+    crate::{
+        bridge_out::{
+            check_accounts_len, check_address, check_pda,
+            mayan::{check_mayan_accounts, mayan_destination_chain, BridgeToMayan, MayanExtLog, MayanOrderKeys},
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs,
+        },
+        common::{mayan_swap_program as MayanSwapProgram, safe_to_fixed_bytes},
+    },
+    anchor_lang::{prelude::*, system_program},
+};
+
+/// Mayan Wormhole swap, the locked tokens are bridged by the token bridge and swapped on the destination.
+/// to_chain_id is the wormhole chain id, enabled per chain in chain_registry under this adaptor id.
+pub struct MayanWhSwapAdaptor;
+
+impl BridgeAdaptor for MayanWhSwapAdaptor {
+    const ID: AdaptorID = AdaptorID::MAYAN_WH_SWAP;
+    const ACCOUNTS_LEN: usize = 3;
+    type Args = BridgeToMayanWhSwapArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToMayanWhSwapArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        mayan_destination_chain(data.to_chain_id)?;
+        let accounts = ctx.remaining_accounts;
+        // state, PDA: seeds = [b"SWAP_STATE", payer, random_key], swap_program
+        check_pda(
+            &accounts[0],
+            &[b"SWAP_STATE", ctx.accounts.payer.key().as_ref(), &args.random_key],
+            &MayanSwapProgram::id()
+        )?;
+        check_address(&accounts[2], &MayanSwapProgram::id())?;
+        check_mayan_accounts(ctx, &accounts[0], &accounts[1])
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    data: BridgeToArgs,
+    bridge_to_mayan_wh_swap_args: BridgeToMayanWhSwapArgs,
+) -> Result<BridgeResult> {
+    let params = MayanWhSwapParams {
+        amount_in: data.amount,
+        dest_address: safe_to_fixed_bytes::<32>(data.to)?,
+        destination_chain: mayan_destination_chain(data.to_chain_id)?,
+        token_out: bridge_to_mayan_wh_swap_args.token_out,
+        amount_out_min: bridge_to_mayan_wh_swap_args.amount_out_min,
+        gas_drop: bridge_to_mayan_wh_swap_args.gas_drop,
+        fee_swap: bridge_to_mayan_wh_swap_args.fee_swap,
+        fee_return: bridge_to_mayan_wh_swap_args.fee_return,
+        fee_cancel: bridge_to_mayan_wh_swap_args.fee_cancel,
+        deadline: bridge_to_mayan_wh_swap_args.deadline,
+        ref_address: bridge_to_mayan_wh_swap_args.ref_address,
+        fee_rate_ref: bridge_to_mayan_wh_swap_args.fee_rate_ref,
+        random_key: bridge_to_mayan_wh_swap_args.random_key,
+    };
+
+    let bridge_to_mayan = BridgeToMayan::from_ctx(&ctx);
+    bridge_to_mayan.lock_and_invoke(&ctx, data.amount, params.encode()?, init_swap_order_metas(&bridge_to_mayan.keys()), &[])?;
+
+    let mayan_log = MayanExtLog::from_args(
+        bridge_to_mayan_wh_swap_args.gas_drop,
+        bridge_to_mayan_wh_swap_args.ref_address,
+        bridge_to_mayan_wh_swap_args.fee_rate_ref,
+    );
+    Ok(BridgeResult{
+        ext: serde_json::to_string(&mayan_log).unwrap(),
+    })
+}
+
+/// init_swap_order accounts of the swap program, the state takes the locked tokens from its token account.
+pub fn init_swap_order_metas(keys: &MayanOrderKeys) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(keys.trader, true),
+        AccountMeta::new(keys.state, false),
+        AccountMeta::new_readonly(keys.state_account, false),
+        AccountMeta::new_readonly(keys.mint, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ]
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BridgeToMayanWhSwapArgs {
+    pub token_out: [u8; 32],    // token received on the destination
+    pub amount_out_min: u64,    // min amount of token_out, in wormhole 8 decimals
+    pub fee_swap: u64,          // paid to the relayer swapping on solana
+    pub fee_return: u64,        // paid to the relayer redeeming on the destination
+    pub fee_cancel: u64,        // paid to the relayer refunding a failed swap
+    pub deadline: u64,          // swap is refunded after this timestamp
+    pub gas_drop: u64,          // native gas delivered to the recipient on the destination
+    pub ref_address: [u8; 32],  // referrer receiving fee_rate_ref of the amount
+    pub fee_rate_ref: u8,       // referrer fee, in bps
+    pub random_key: [u8; 32],   // seed of the state PDA
+}
+
+impl BridgeToMayanWhSwapArgs {
+    pub fn try_from_vec(data: &[u8]) -> Result<BridgeToMayanWhSwapArgs> {
+        let decoded_args = BridgeToMayanWhSwapArgs::try_from_slice(&data)?;
+        Ok(decoded_args)
+    }
+}
+
+// init_swap_order params of the swap program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MayanWhSwapParams {
+    pub amount_in: u64,
+    pub dest_address: [u8; 32],
+    pub destination_chain: u16,
+    pub token_out: [u8; 32],
+    pub amount_out_min: u64,
+    pub gas_drop: u64,
+    pub fee_swap: u64,
+    pub fee_return: u64,
+    pub fee_cancel: u64,
+    pub deadline: u64,
+    pub ref_address: [u8; 32],
+    pub fee_rate_ref: u8,
+    pub random_key: [u8; 32],
+}
+
+impl MayanWhSwapParams {
+    pub const INSTRUCTION_BYTES: [u8; 8] = [70, 253, 235, 223, 117, 115, 204, 19];
+
+    // init_swap_order instruction data, the sighash then the borsh params
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut data = Self::INSTRUCTION_BYTES.to_vec();
+        data.extend_from_slice(&self.try_to_vec()?);
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::solana_program::hash as hash;
+
+    #[test]
+    fn sighash() {
+        let mut sighash = [0u8; 8];
+        sighash.copy_from_slice(&hash::hash(b"global:init_swap_order").to_bytes()[..8]);
+        assert_eq!(sighash, MayanWhSwapParams::INSTRUCTION_BYTES);
+    }
+
+    #[test]
+    fn encode_params() {
+        let params = MayanWhSwapParams {
+            amount_in: 1,
+            dest_address: [2; 32],
+            destination_chain: 30,
+            token_out: [3; 32],
+            amount_out_min: 4,
+            gas_drop: 5,
+            fee_swap: 6,
+            fee_return: 7,
+            fee_cancel: 8,
+            deadline: 9,
+            ref_address: [10; 32],
+            fee_rate_ref: 11,
+            random_key: [12; 32],
+        };
+        let data = params.encode().unwrap();
+        assert_eq!(data.len(), 195);
+        assert_eq!(data[..8], MayanWhSwapParams::INSTRUCTION_BYTES);
+        assert_eq!(data[8..16], 1u64.to_le_bytes());
+        assert_eq!(data[16..48], [2; 32]);
+        assert_eq!(data[48..50], 30u16.to_le_bytes());
+        assert_eq!(data[50..82], [3; 32]);
+        assert_eq!(data[82..90], 4u64.to_le_bytes());
+        assert_eq!(data[90..98], 5u64.to_le_bytes());
+        assert_eq!(data[122..130], 9u64.to_le_bytes());
+        assert_eq!(data[130..162], [10; 32]);
+        assert_eq!(data[162], 11);
+        assert_eq!(data[163..], [12; 32]);
+    }
+}
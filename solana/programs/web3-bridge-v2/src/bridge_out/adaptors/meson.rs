@@ -1,7 +1,8 @@
 use {
     crate::{
         bridge_out::{
-            BridgeResult, BridgeTo, BridgeToArgs, SwapType
+            check_accounts_len, check_address, check_pda, check_writable,
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs, SwapType
         },
         common::{
             meson_program as Meson, vec_to_hex_string, MesonErrorCode
@@ -20,6 +21,50 @@ use {
     serde::Serialize,
 };
 
+pub struct MesonAdaptor;
+
+impl BridgeAdaptor for MesonAdaptor {
+    const ID: AdaptorID = AdaptorID::MESON;
+    const ACCOUNTS_LEN: usize = 5;
+    const CHAIN_REGISTRY_REQUIRED: bool = false;
+    type Args = BridgeToMesonArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToMesonArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        _data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        let accounts = ctx.remaining_accounts;
+        let meson_program = Meson::id();
+        // meson_contract_signer, PDA: seeds = [b"contract_signer"]
+        check_pda(&accounts[0], &[b"contract_signer"], &meson_program)?;
+        check_writable(&accounts[0])?;
+        // meson_token_account
+        check_writable(&accounts[1])?;
+        // supported_token_account, PDA: seeds = [b"supported_tokens"]
+        check_pda(&accounts[2], &[b"supported_tokens"], &meson_program)?;
+        check_writable(&accounts[2])?;
+        // posted_token_account, PDA: seeds = [b"posted_swap", encoded]
+        check_pda(&accounts[3], &[b"posted_swap", &args.encoded], &meson_program)?;
+        check_writable(&accounts[3])?;
+        // meson_program
+        check_address(&accounts[4], &meson_program)
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
 pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: BridgeToArgs, bridge_to_meson_args: BridgeToMesonArgs) -> Result<BridgeResult> {
 
     require!(data.swap_type == SwapType::BRIDGE, MesonErrorCode::MesonDoNotSupportSwapType);
@@ -30,9 +75,6 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: Br
     new_data.append(&mut encoded_clone);
     new_data.append(&mut initiator_clone);
     new_data.append(&mut vec![00u8,00u8,00u8,00u8,00u8,00u8,00u8,01u8]);
-    let encoded_clone = bridge_to_meson_args.encoded.clone();
-    let initiator_clone = bridge_to_meson_args.initiator.clone();
-
 
     let bridge_to_meson = BridgeToMeson{
         // account in bridge_to.accounts
@@ -73,10 +115,9 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: Br
     )?;
 
     let meson_log = MesonExtLog{
-        encoded: "0x".to_string() + &vec_to_hex_string(encoded_clone),
-        initiator: "0x".to_string() + &vec_to_hex_string(initiator_clone),
+        encoded: "0x".to_string() + &vec_to_hex_string(bridge_to_meson_args.encoded.clone()),
+        initiator: "0x".to_string() + &vec_to_hex_string(bridge_to_meson_args.initiator.clone()),
     };
-
     Ok(BridgeResult{
         ext: serde_json::to_string(&meson_log).unwrap(),
     })
@@ -0,0 +1,496 @@
+use {
+    crate::{
+        bridge_out::{
+            check_accounts_len, check_address, check_pda, check_writable,
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs, NttManagerRegistry, NTT_MODE_BURNING,
+        },
+        common::{
+            safe_to_u16, wormhole_core_program as Wormhole, XBridgeErrorCode,
+        }
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{instruction::Instruction, program::{invoke, invoke_signed}, sysvar},
+    },
+    anchor_spl::{
+        associated_token::get_associated_token_address,
+        token::{Mint, TokenAccount},
+    },
+    serde::Serialize,
+    tiny_keccak::{Hasher, Keccak},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BridgeToNttArgs {
+    pub manager: Pubkey,        // NTT manager program of the mint, must match the registry
+    pub nonce: u64,             // seed of the outbox_item PDA with the payer, unique per transfer of the payer
+    pub should_queue: bool,     // queue the transfer when the outbound rate limit is exceeded
+}
+
+impl BridgeToNttArgs {
+    pub fn try_from_vec(data: &[u8]) -> Result<BridgeToNttArgs> {
+        let decoded_args = BridgeToNttArgs::try_from_slice(&data)?;
+        Ok(decoded_args)
+    }
+}
+
+// TransferArgs of the NTT manager
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct NttTransferArgs {
+    pub amount: u64,
+    pub recipient_chain: u16,
+    pub recipient_address: [u8; 32],
+    pub should_queue: bool,
+}
+
+impl NttTransferArgs {
+    pub fn new(data: &BridgeToArgs, args: &BridgeToNttArgs) -> Result<NttTransferArgs> {
+        Ok(NttTransferArgs {
+            amount: data.amount,
+            recipient_chain: safe_to_u16(data.to_chain_id)?,
+            recipient_address: <[u8; 32]>::try_from(data.to.as_slice())
+                .map_err(|_| XBridgeErrorCode::InvalidRecipientLength)?,
+            should_queue: args.should_queue,
+        })
+    }
+
+    // session_authority, PDA: seeds = [b"session_authority", sender, keccak256(args)]
+    pub fn hash(&self) -> [u8; 32] {
+        let mut hasher = Keccak::v256();
+        let mut hash = [0u8; 32];
+        hasher.update(&self.try_to_vec().unwrap());
+        hasher.finalize(&mut hash);
+        hash
+    }
+}
+
+// ReleaseOutboundArgs of the wormhole transceiver
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ReleaseOutboundArgs {
+    pub revert_on_delay: bool,
+}
+
+// seeded by the payer, another payer cannot take the nonce first
+pub fn outbox_item_address(payer: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"ntt_outbox", payer.as_ref(), &nonce.to_le_bytes()], &crate::ID)
+}
+
+// the manager seeds its per-chain accounts with the wormhole chain id in big endian
+pub fn chain_address(prefix: &[u8], chain: u16, manager: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[prefix, &chain.to_be_bytes()], manager)
+}
+
+#[derive(Accounts)]
+pub struct BridgeToNtt<'info> {
+    #[account(mut)]
+    /// Payer.
+    /// Owner of the transferred tokens and payer of the outbox_item rent.
+    pub payer: Signer<'info>,
+
+    /// NTT Manager Registry
+    /// PDA: seeds = [b"ntt_manager", mint], set by set_ntt_manager.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub ntt_manager_registry: AccountInfo<'info>,
+
+    /// Config Account
+    /// PDA: seeds = [b"config"], seeds::program = manager.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub config: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// tokenMint
+    /// give token mint
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    /// User Token Account
+    /// ATA: mint = mint, owner = payer,
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    /// Outbox Item Account
+    /// PDA: seeds = [b"ntt_outbox", payer, nonce], seeds::program = web3_bridge_v2.
+    /// CHECK: Initialized by the manager, signed by this program.
+    pub outbox_item: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Outbox Rate Limit Account
+    /// PDA: seeds = [b"outbox_rate_limit"], seeds::program = manager.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub outbox_rate_limit: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Custody Account
+    /// ATA: mint = mint, owner = token_authority.
+    /// CHECK: This account is an ATA account, which is used for ntt.
+    pub custody: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Inbox Rate Limit Account
+    /// PDA: seeds = [b"inbox_rate_limit", recipient_chain], seeds::program = manager.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub inbox_rate_limit: AccountInfo<'info>,
+
+    /// Peer Account
+    /// PDA: seeds = [b"peer", recipient_chain], seeds::program = manager.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub peer: AccountInfo<'info>,
+
+    /// Session Authority Account
+    /// PDA: seeds = [b"session_authority", payer, keccak256(transfer_args)], seeds::program = manager.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub session_authority: AccountInfo<'info>,
+
+    /// Token Authority Account
+    /// PDA: seeds = [b"token_authority"], seeds::program = manager.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub token_authority: AccountInfo<'info>,
+
+    /// NTT Manager Program
+    /// CHECK: checked against the ntt_manager_registry
+    pub manager_program: AccountInfo<'info>,
+
+    /// Registered Transceiver Account
+    /// PDA: seeds = [b"registered_transceiver", transceiver_program], seeds::program = manager.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub transceiver: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Wormhole Message Account
+    /// PDA: seeds = [b"message", outbox_item], seeds::program = transceiver_program.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub wormhole_message: AccountInfo<'info>,
+
+    /// Emitter Account
+    /// PDA: seeds = [b"emitter"], seeds::program = transceiver_program.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub emitter: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Wormhole Bridge Account
+    /// PDA: seeds = [b"Bridge"], seeds::program = wormhole_core_program.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub wormhole_bridge: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Wormhole Fee Collector Account
+    /// PDA: seeds = [b"fee_collector"], seeds::program = wormhole_core_program.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub wormhole_fee_collector: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// Wormhole Sequence Account
+    /// PDA: seeds = [b"Sequence", emitter], seeds::program = wormhole_core_program.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub wormhole_sequence: AccountInfo<'info>,
+
+    /// Wormhole Core Program
+    /// "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth"
+    /// CHECK: fixed
+    pub wormhole_program: AccountInfo<'info>,
+
+    /// Clock Sysvar
+    /// CHECK: fixed
+    pub clock: AccountInfo<'info>,
+
+    /// Rent Sysvar
+    /// CHECK: fixed
+    pub rent: AccountInfo<'info>,
+
+    /// Outbox Item Signer Account
+    /// PDA: seeds = [b"outbox_item_signer"], seeds::program = transceiver_program.
+    /// CHECK: This account is a PDA account, which is used for ntt.
+    pub outbox_item_signer: AccountInfo<'info>,
+
+    /// Wormhole Transceiver Program
+    /// CHECK: checked against the ntt_manager_registry
+    pub transceiver_program: AccountInfo<'info>,
+
+    /// SPL Token Program
+    /// "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+    /// CHECK: fixed
+    pub token_program: AccountInfo<'info>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NttExtLog {
+    manager: String,
+    outbox_item: String,
+    should_queue: bool,
+}
+
+/// to_chain_id is the Wormhole chain id, mapped by chain_registry like the wormhole adaptor.
+pub struct NttAdaptor;
+
+impl BridgeAdaptor for NttAdaptor {
+    const ID: AdaptorID = AdaptorID::WORMHOLE_NTT;
+    const ACCOUNTS_LEN: usize = 21;
+    type Args = BridgeToNttArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToNttArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        let accounts = ctx.remaining_accounts;
+        let mint = ctx.accounts.mint.key();
+        // ntt_manager_registry, PDA: seeds = [b"ntt_manager", mint], web3_bridge_v2
+        check_pda(&accounts[0], &[b"ntt_manager", mint.as_ref()], &crate::ID)?;
+        require_keys_eq!(*accounts[0].owner, crate::ID, XBridgeErrorCode::InvalidAccount);
+        let registry = NttManagerRegistry::try_deserialize(&mut &accounts[0].try_borrow_data()?[..])?;
+        require!(registry.enabled, XBridgeErrorCode::NttManagerNotEnabled);
+        require_keys_eq!(registry.manager, args.manager, XBridgeErrorCode::NttManagerMismatch);
+
+        let manager = registry.manager;
+        let transceiver = registry.transceiver;
+        let core_bridge = Wormhole::id();
+        let transfer_args = NttTransferArgs::new(data, args)?;
+        // config, PDA: seeds = [b"config"], manager
+        check_pda(&accounts[1], &[b"config"], &manager)?;
+        // outbox_item, PDA: seeds = [b"ntt_outbox", payer, nonce], web3_bridge_v2
+        check_address(&accounts[2], &outbox_item_address(&ctx.accounts.payer.key(), args.nonce).0)?;
+        check_writable(&accounts[2])?;
+        // outbox_rate_limit, PDA: seeds = [b"outbox_rate_limit"], manager
+        check_pda(&accounts[3], &[b"outbox_rate_limit"], &manager)?;
+        check_writable(&accounts[3])?;
+        // token_authority, PDA: seeds = [b"token_authority"], manager
+        check_pda(&accounts[8], &[b"token_authority"], &manager)?;
+        // custody, ATA: mint = mint, owner = token_authority
+        check_address(&accounts[4], &get_associated_token_address(accounts[8].key, &mint))?;
+        check_writable(&accounts[4])?;
+        // inbox_rate_limit, PDA: seeds = [b"inbox_rate_limit", recipient_chain (be)], manager
+        check_address(&accounts[5], &chain_address(b"inbox_rate_limit", transfer_args.recipient_chain, &manager).0)?;
+        check_writable(&accounts[5])?;
+        // peer, PDA: seeds = [b"peer", recipient_chain (be)], manager
+        check_address(&accounts[6], &chain_address(b"peer", transfer_args.recipient_chain, &manager).0)?;
+        // session_authority, PDA: seeds = [b"session_authority", payer, keccak256(transfer_args)], manager
+        check_pda(
+            &accounts[7],
+            &[b"session_authority", ctx.accounts.payer.key().as_ref(), &transfer_args.hash()],
+            &manager
+        )?;
+        check_address(&accounts[9], &manager)?;
+        // transceiver, PDA: seeds = [b"registered_transceiver", transceiver_program], manager
+        check_pda(&accounts[10], &[b"registered_transceiver", transceiver.as_ref()], &manager)?;
+        // wormhole_message, PDA: seeds = [b"message", outbox_item], transceiver_program
+        check_pda(&accounts[11], &[b"message", accounts[2].key.as_ref()], &transceiver)?;
+        check_writable(&accounts[11])?;
+        // emitter, PDA: seeds = [b"emitter"], transceiver_program
+        check_pda(&accounts[12], &[b"emitter"], &transceiver)?;
+        // wormhole_bridge, PDA: seeds = [b"Bridge"], core_bridge_program
+        check_pda(&accounts[13], &[b"Bridge"], &core_bridge)?;
+        check_writable(&accounts[13])?;
+        // wormhole_fee_collector, PDA: seeds = [b"fee_collector"], core_bridge_program
+        check_pda(&accounts[14], &[b"fee_collector"], &core_bridge)?;
+        check_writable(&accounts[14])?;
+        // wormhole_sequence, PDA: seeds = [b"Sequence", emitter], core_bridge_program
+        check_pda(&accounts[15], &[b"Sequence", accounts[12].key.as_ref()], &core_bridge)?;
+        check_writable(&accounts[15])?;
+        check_address(&accounts[16], &core_bridge)?;
+        check_address(&accounts[17], &sysvar::clock::id())?;
+        check_address(&accounts[18], &sysvar::rent::id())?;
+        // outbox_item_signer, PDA: seeds = [b"outbox_item_signer"], transceiver_program
+        check_pda(&accounts[19], &[b"outbox_item_signer"], &transceiver)?;
+        check_address(&accounts[20], &transceiver)
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    data: BridgeToArgs,
+    bridge_to_ntt_args: BridgeToNttArgs,
+) -> Result<BridgeResult> {
+    let bridge_to_ntt = BridgeToNtt {
+        // account in bridge_to.accounts
+        payer: ctx.accounts.payer.clone(),
+        mint: ctx.accounts.mint.clone(),
+        user_token_account: ctx.accounts.user_token_account.clone(),
+        token_program: ctx.accounts.token_program.clone(),
+        system_program: ctx.accounts.system_program.clone(),
+        // account in bridge_to.remaining_accounts
+        ntt_manager_registry: ctx.remaining_accounts[0].to_account_info(),
+        config: ctx.remaining_accounts[1].to_account_info(),
+        outbox_item: ctx.remaining_accounts[2].to_account_info(),
+        outbox_rate_limit: ctx.remaining_accounts[3].to_account_info(),
+        custody: ctx.remaining_accounts[4].to_account_info(),
+        inbox_rate_limit: ctx.remaining_accounts[5].to_account_info(),
+        peer: ctx.remaining_accounts[6].to_account_info(),
+        session_authority: ctx.remaining_accounts[7].to_account_info(),
+        token_authority: ctx.remaining_accounts[8].to_account_info(),
+        manager_program: ctx.remaining_accounts[9].to_account_info(),
+        transceiver: ctx.remaining_accounts[10].to_account_info(),
+        wormhole_message: ctx.remaining_accounts[11].to_account_info(),
+        emitter: ctx.remaining_accounts[12].to_account_info(),
+        wormhole_bridge: ctx.remaining_accounts[13].to_account_info(),
+        wormhole_fee_collector: ctx.remaining_accounts[14].to_account_info(),
+        wormhole_sequence: ctx.remaining_accounts[15].to_account_info(),
+        wormhole_program: ctx.remaining_accounts[16].to_account_info(),
+        clock: ctx.remaining_accounts[17].to_account_info(),
+        rent: ctx.remaining_accounts[18].to_account_info(),
+        outbox_item_signer: ctx.remaining_accounts[19].to_account_info(),
+        transceiver_program: ctx.remaining_accounts[20].to_account_info(),
+    };
+    let registry = NttManagerRegistry::try_deserialize(
+        &mut &bridge_to_ntt.ntt_manager_registry.try_borrow_data()?[..]
+    )?;
+    let transfer_args = NttTransferArgs::new(&data, &bridge_to_ntt_args)?;
+
+    // 01.Delegate spending to the manager's session authority.
+    anchor_spl::token::approve(
+        CpiContext::new(
+            bridge_to_ntt.token_program.to_account_info(),
+            anchor_spl::token::Approve {
+                to: bridge_to_ntt.user_token_account.to_account_info(),
+                delegate: bridge_to_ntt.session_authority.to_account_info(),
+                authority: bridge_to_ntt.payer.to_account_info(),
+            },
+        ),
+        data.amount,
+    )?;
+
+    // 02.transfer_burn / transfer_lock, the outbox_item is created by the manager
+    let mut account_metas = vec![
+        AccountMeta::new(bridge_to_ntt.payer.key(), true),
+        AccountMeta::new_readonly(bridge_to_ntt.config.key(), false),
+        AccountMeta::new(bridge_to_ntt.mint.key(), false),
+        AccountMeta::new(bridge_to_ntt.user_token_account.key(), false),
+        AccountMeta::new_readonly(bridge_to_ntt.token_program.key(), false),
+        AccountMeta::new(bridge_to_ntt.outbox_item.key(), true),
+        AccountMeta::new(bridge_to_ntt.outbox_rate_limit.key(), false),
+        AccountMeta::new(bridge_to_ntt.custody.key(), false),
+        AccountMeta::new_readonly(bridge_to_ntt.system_program.key(), false),
+        AccountMeta::new(bridge_to_ntt.inbox_rate_limit.key(), false),
+        AccountMeta::new_readonly(bridge_to_ntt.peer.key(), false),
+        AccountMeta::new_readonly(bridge_to_ntt.session_authority.key(), false),
+    ];
+    let mut new_data = if registry.mode == NTT_MODE_BURNING {
+        account_metas.push(AccountMeta::new_readonly(bridge_to_ntt.token_authority.key(), false));
+        vec![75u8, 144u8, 26u8, 232u8, 39u8, 12u8, 75u8, 222u8]
+    } else {
+        vec![179u8, 158u8, 146u8, 148u8, 151u8, 46u8, 176u8, 200u8]
+    };
+    new_data.extend_from_slice(&transfer_args.try_to_vec()?);
+
+    let ix = Instruction {
+        program_id: registry.manager,
+        data: new_data,
+        accounts: account_metas,
+    };
+
+    let nonce_binding = bridge_to_ntt_args.nonce.to_le_bytes();
+    let payer_key = bridge_to_ntt.payer.key();
+    let (_, outbox_item_bump) = outbox_item_address(&payer_key, bridge_to_ntt_args.nonce);
+    let outbox_item_signer: &[&[u8]] = &[b"ntt_outbox", payer_key.as_ref(), &nonce_binding, &[outbox_item_bump]];
+
+    invoke_signed(
+        &ix,
+        &bridge_to_ntt.to_account_infos(),
+        &[outbox_item_signer]
+    )?;
+
+    // 03.release the outbox_item through the wormhole transceiver,
+    // a queued or rate limited transfer is left in the outbox and released later
+    let mut new_data = vec![202u8, 87u8, 51u8, 173u8, 142u8, 160u8, 188u8, 204u8];
+    new_data.extend_from_slice(&ReleaseOutboundArgs { revert_on_delay: false }.try_to_vec()?);
+
+    let ix = Instruction {
+        program_id: registry.transceiver,
+        data: new_data,
+        accounts: vec![
+            AccountMeta::new(bridge_to_ntt.payer.key(), true),
+            AccountMeta::new_readonly(bridge_to_ntt.config.key(), false),
+            AccountMeta::new(bridge_to_ntt.outbox_item.key(), false),
+            AccountMeta::new_readonly(bridge_to_ntt.transceiver.key(), false),
+            AccountMeta::new(bridge_to_ntt.wormhole_message.key(), false),
+            AccountMeta::new_readonly(bridge_to_ntt.emitter.key(), false),
+            AccountMeta::new(bridge_to_ntt.wormhole_bridge.key(), false),
+            AccountMeta::new(bridge_to_ntt.wormhole_fee_collector.key(), false),
+            AccountMeta::new(bridge_to_ntt.wormhole_sequence.key(), false),
+            AccountMeta::new_readonly(bridge_to_ntt.wormhole_program.key(), false),
+            AccountMeta::new_readonly(bridge_to_ntt.system_program.key(), false),
+            AccountMeta::new_readonly(bridge_to_ntt.clock.key(), false),
+            AccountMeta::new_readonly(bridge_to_ntt.rent.key(), false),
+            AccountMeta::new_readonly(bridge_to_ntt.outbox_item_signer.key(), false),
+            AccountMeta::new_readonly(bridge_to_ntt.manager_program.key(), false),
+        ],
+    };
+
+    invoke(
+        &ix,
+        &bridge_to_ntt.to_account_infos()
+    )?;
+
+    msg!("Ntt outbox_item: {}", bridge_to_ntt.outbox_item.key());
+
+    let ntt_log = NttExtLog {
+        manager: bridge_to_ntt_args.manager.to_string(),
+        outbox_item: bridge_to_ntt.outbox_item.key().to_string(),
+        should_queue: bridge_to_ntt_args.should_queue,
+    };
+    Ok(BridgeResult{
+        ext: serde_json::to_string(&ntt_log).unwrap(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::solana_program::hash as hash;
+
+    #[test]
+    fn sighash() {
+        let mut sighash = [0u8; 8];
+        sighash.copy_from_slice(&hash::hash(b"global:transfer_burn").to_bytes()[..8]);
+        assert_eq!(sighash, [75u8, 144u8, 26u8, 232u8, 39u8, 12u8, 75u8, 222u8]);
+        sighash.copy_from_slice(&hash::hash(b"global:release_wormhole_outbound").to_bytes()[..8]);
+        assert_eq!(sighash, [202u8, 87u8, 51u8, 173u8, 142u8, 160u8, 188u8, 204u8]);
+    }
+
+    #[test]
+    fn encode_transfer_args() {
+        let transfer_args = NttTransferArgs {
+            amount: 1_000_000,
+            recipient_chain: 2,
+            recipient_address: [1; 32],
+            should_queue: true,
+        };
+        // u64 + u16 + [u8; 32] + bool
+        assert_eq!(transfer_args.try_to_vec().unwrap().len(), 8 + 2 + 32 + 1);
+        assert_ne!(transfer_args.hash(), [0u8; 32]);
+    }
+
+    #[test]
+    fn peer_address() {
+        // peer of Ethereum (wormhole chain 2) on the mainnet NTT manager program, seeded big endian
+        let manager = "NTtAaoDJhkeHeaVUHnyhwbPNAN6WgBpHkHBTc6d7vLK".parse::<Pubkey>().unwrap();
+        assert_eq!(chain_address(b"peer", 2, &manager).0, "J835ujXLBsfwjnHuqjjGYkW8GPPNbzvUttjPkD8MzT1p".parse::<Pubkey>().unwrap());
+        let (le_peer, _) = Pubkey::find_program_address(&[b"peer", &2u16.to_le_bytes()], &manager);
+        assert_ne!(chain_address(b"peer", 2, &manager).0, le_peer);
+    }
+
+    #[test]
+    fn outbox_item_per_payer() {
+        let payer = Pubkey::new_unique();
+        assert_eq!(outbox_item_address(&payer, 1), outbox_item_address(&payer, 1));
+        assert_ne!(outbox_item_address(&payer, 1).0, outbox_item_address(&payer, 2).0);
+        assert_ne!(outbox_item_address(&payer, 1).0, outbox_item_address(&Pubkey::new_unique(), 1).0);
+    }
+}
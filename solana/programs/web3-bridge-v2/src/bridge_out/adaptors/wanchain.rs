@@ -1,9 +1,15 @@
 use {
     crate::{
         bridge_out::{
+            check_accounts_len,
+            check_address,
+            check_pda,
+            check_writable,
+            AdaptorID,
+            BridgeAdaptor,
+            BridgeResult,
             BridgeTo,
             BridgeToArgs,
-            BridgeResult,
         },
         common::{
             wanchain_sol_value,
@@ -28,6 +34,50 @@ use {
     },
 };
 
+pub struct WanchainAdaptor;
+
+impl BridgeAdaptor for WanchainAdaptor {
+    const ID: AdaptorID = AdaptorID::WANCHAIN;
+    const ACCOUNTS_LEN: usize = 8;
+    const CHAIN_REGISTRY_REQUIRED: bool = false;
+    type Args = BridgeToWanchainArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToWanchainArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        _data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        let accounts = ctx.remaining_accounts;
+        check_address(&accounts[0], &wanchain_program::id())?;
+        check_address(&accounts[1], &wanchain_sol_value::id())?;
+        check_writable(&accounts[1])?;
+        // token_vault
+        check_writable(&accounts[2])?;
+        check_address(&accounts[3], &wanchain_fee_receiver::id())?;
+        check_writable(&accounts[3])?;
+        check_address(&accounts[4], &wanchain_admin_board_program::id())?;
+        check_address(&accounts[5], &wanchain_config_account::id())?;
+        // token_pair_account, PDA: seeds = [b"TokenPairInfo", token_pair_id], admin_board_program
+        check_pda(&accounts[6], &[b"TokenPairInfo", &args.token_pair_id.to_le_bytes()], &wanchain_admin_board_program::id())?;
+        check_writable(&accounts[6])?;
+        // cctp_admin_board_fee_account, PDA: seeds = [b"FeeData", slip44_chain_id], circle_config_program
+        check_pda(&accounts[7], &[b"FeeData", &args.slip44_chain_id.to_le_bytes()], &wanchain_circle_config_program::id())
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+}
+
 pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: BridgeToArgs, bridge_to_wanchain_args: BridgeToWanchainArgs) -> Result<BridgeResult> {
 
     let bridge_to_wanchain = BridgeToWanchain{
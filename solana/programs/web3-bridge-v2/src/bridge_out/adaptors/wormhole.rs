@@ -1,16 +1,20 @@
 use {
     crate::{
         bridge_out::{
-            BridgeResult, BridgeTo, BridgeToArgs, SwapType
+            check_accounts_len, check_address, check_pda, check_writable,
+            AdaptorID, BridgeAdaptor, BridgeResult, BridgeTo, BridgeToArgs, RelayerFee, SwapType
         },
         common::{
-            wormhole_core_program as Wormhole, wormhole_token_bridge_program as TokenBridge, wrapped_sol
+            safe_to_fixed_bytes,
+            wormhole_core_program as Wormhole, wormhole_token_bridge_program as TokenBridge,
+            wormhole_token_bridge_relayer_program as TokenBridgeRelayer, wrapped_sol,
+            XBridgeErrorCode
         }
     },
     anchor_lang::{
         prelude::*, 
         solana_program::{
-            instruction::Instruction, program::invoke_signed, system_program, sysvar
+            instruction::Instruction, program::{invoke, invoke_signed}, system_program, sysvar
         },
     },
     anchor_spl::{
@@ -20,7 +24,121 @@ use {
 };
 
 
+pub struct WormholeAdaptor;
+
+impl BridgeAdaptor for WormholeAdaptor {
+    const ID: AdaptorID = AdaptorID::WORMHOLE;
+    const ACCOUNTS_LEN: usize = 13;
+    type Args = BridgeToWromholeArgs;
+
+    fn decode_args(data: &BridgeToArgs) -> Result<Self::Args> {
+        BridgeToWromholeArgs::try_from_vec(&data.data)
+    }
+
+    fn validate_accounts<'info>(
+        ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: &BridgeToArgs,
+        args: &Self::Args,
+    ) -> Result<()> {
+        // the relayer fee is paid out of the bridged amount on the destination chain
+        require!(args.relayer_fee < data.amount, XBridgeErrorCode::InvalidRelayerFee);
+        // a payload is redeemed by its own redeemer, not by a relayer
+        require!(args.payload.is_empty() || args.relayer_fee == 0, XBridgeErrorCode::InvalidRelayerFee);
+        if args.mode == WormholeMode::RELAYER {
+            return validate_relay_accounts(ctx, data);
+        }
+        check_accounts_len(ctx.remaining_accounts, Self::ACCOUNTS_LEN)?;
+        let accounts = ctx.remaining_accounts;
+        let token_bridge = TokenBridge::id();
+        let core_bridge = Wormhole::id();
+        // config, PDA: seeds = [b"config"], token_bridge_program
+        check_pda(&accounts[0], &[b"config"], &token_bridge)?;
+        if is_wrapped_mint(&accounts[1], &ctx.accounts.mint.key()) {
+            // wrapped_meta, PDA: seeds = [b"meta", mint], token_bridge_program
+            check_pda(&accounts[1], &[b"meta", ctx.accounts.mint.key().as_ref()], &token_bridge)?;
+        } else {
+            // custody_token, PDA: seeds = [mint], token_bridge_program
+            check_pda(&accounts[1], &[ctx.accounts.mint.key().as_ref()], &token_bridge)?;
+            check_writable(&accounts[1])?;
+        }
+        // transfer_authority, PDA: seeds = [b"authority_signer"], token_bridge_program
+        check_pda(&accounts[2], &[b"authority_signer"], &token_bridge)?;
+        // custody_authority, PDA: seeds = [b"custody_signer"], token_bridge_program
+        check_pda(&accounts[3], &[b"custody_signer"], &token_bridge)?;
+        // core_bridge_config, PDA: seeds = [b"Bridge"], core_bridge_program
+        check_pda(&accounts[4], &[b"Bridge"], &core_bridge)?;
+        check_writable(&accounts[4])?;
+        // core_message, PDA: seeds = [b"bridged", nonce], web3_bridge_v2
+        check_pda(&accounts[5], &[b"bridged", &args.nonce.to_le_bytes()], &crate::ID)?;
+        check_writable(&accounts[5])?;
+        // core_emitter, PDA: seeds = [b"emitter"], token_bridge_program
+        check_pda(&accounts[6], &[b"emitter"], &token_bridge)?;
+        // core_emitter_sequence, PDA: seeds = [b"Sequence", core_emitter], core_bridge_program
+        check_pda(&accounts[7], &[b"Sequence", accounts[6].key.as_ref()], &core_bridge)?;
+        check_writable(&accounts[7])?;
+        // core_fee_collector, PDA: seeds = [b"fee_collector"], core_bridge_program
+        check_pda(&accounts[8], &[b"fee_collector"], &core_bridge)?;
+        check_writable(&accounts[8])?;
+        check_address(&accounts[9], &sysvar::clock::id())?;
+        check_address(&accounts[10], &sysvar::rent::id())?;
+        check_address(&accounts[11], &core_bridge)?;
+        check_address(&accounts[12], &token_bridge)
+    }
+
+    fn execute<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+        data: BridgeToArgs,
+        args: Self::Args,
+    ) -> Result<BridgeResult> {
+        handler(ctx, data, args)
+    }
+
+    // Wormhole rounds down decimal places when transferring WSOL across chains
+    fn expected_consumed(mint: &Pubkey, amount: u64) -> Result<u64> {
+        if *mint != wrapped_sol::ID {
+            return Ok(amount);
+        }
+        let adjusted_amount = amount
+            .checked_div(10)
+            .ok_or(XBridgeErrorCode::CalculationError)?
+            .checked_mul(10)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        Ok(adjusted_amount)
+    }
+}
+
+// token_bridge_relayer_program, followed by its transfer_*_tokens_with_relay accounts:
+// payer, payer_sequence, config, foreign_contract, mint, from_token_account, ...
+// the accounts from registered_token on are checked by the relayer and the token bridge
+fn validate_relay_accounts<'info>(ctx: &Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: &BridgeToArgs) -> Result<()> {
+    check_accounts_len(ctx.remaining_accounts, 1 + RELAY_CHECKED_ACCOUNTS_LEN)?;
+    let accounts = ctx.remaining_accounts;
+    let relayer = TokenBridgeRelayer::id();
+    check_address(&accounts[0], &relayer)?;
+    check_address(&accounts[1], &ctx.accounts.payer.key())?;
+    // config, PDA: seeds = [b"sender"], token_bridge_relayer_program
+    check_pda(&accounts[3], &[b"sender"], &relayer)?;
+    // foreign_contract, PDA: seeds = [b"foreign_contract", recipient_chain (be)], token_bridge_relayer_program
+    check_pda(&accounts[4], &[b"foreign_contract", &(data.to_chain_id as u16).to_be_bytes()], &relayer)?;
+    check_address(&accounts[5], &ctx.accounts.mint.key())?;
+    // from_token_account, the relayer pulls the bridged amount from the user token account
+    check_address(&accounts[6], &ctx.accounts.user_token_account.key())?;
+    check_writable(&accounts[6])
+}
+
+// relay accounts checked by validate_relay_accounts, payer to from_token_account
+const RELAY_CHECKED_ACCOUNTS_LEN: usize = 6;
+
+// a wormhole wrapped mint has a wrapped_meta account, created by the token bridge with the mint
+pub fn is_wrapped_mint(account: &AccountInfo, mint: &Pubkey) -> bool {
+    let (wrapped_meta, _) = Pubkey::find_program_address(&[b"meta", mint.as_ref()], &TokenBridge::id());
+    account.key() == wrapped_meta && account.owner == &TokenBridge::id() && !account.data_is_empty()
+}
+
 pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: BridgeToArgs, bridge_to_wormhole_args: BridgeToWromholeArgs) -> Result<BridgeResult> {
+    if bridge_to_wormhole_args.mode == WormholeMode::RELAYER {
+        return relay_handler(ctx, data, bridge_to_wormhole_args);
+    }
 
     // msg!("mint address: {}", ctx.accounts.mint.to_account_info().key);
     let bridge_to_wormhole = BridgeToWormhole {
@@ -101,14 +219,30 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: Br
     )?;
 
     // 03.invoke the wormhole program
-    let mut account_metas = vec![
-        AccountMeta::new(bridge_to_wormhole.payer.key(), true),
-        AccountMeta::new_readonly(bridge_to_wormhole.config.key(), false),
-        AccountMeta::new(bridge_to_wormhole.user_token_account.key(), false),
-        AccountMeta::new(bridge_to_wormhole.mint.key(), false),
-        AccountMeta::new(bridge_to_wormhole.custody_token.key(), false),
-        AccountMeta::new_readonly(bridge_to_wormhole.transfer_authority.key(), false),
-        AccountMeta::new_readonly(bridge_to_wormhole.custody_authority.key(), false),
+    // wrapped mints are burned by the token bridge, native mints are locked in custody
+    let wrapped = is_wrapped_mint(&bridge_to_wormhole.custody_token, &bridge_to_wormhole.mint.key());
+    let mut account_metas = if wrapped {
+        vec![
+            AccountMeta::new(bridge_to_wormhole.payer.key(), true),
+            AccountMeta::new_readonly(bridge_to_wormhole.config.key(), false),
+            AccountMeta::new(bridge_to_wormhole.user_token_account.key(), false),
+            AccountMeta::new_readonly(bridge_to_wormhole.payer.key(), true),
+            AccountMeta::new(bridge_to_wormhole.mint.key(), false),
+            AccountMeta::new_readonly(bridge_to_wormhole.custody_token.key(), false),
+            AccountMeta::new_readonly(bridge_to_wormhole.transfer_authority.key(), false),
+        ]
+    } else {
+        vec![
+            AccountMeta::new(bridge_to_wormhole.payer.key(), true),
+            AccountMeta::new_readonly(bridge_to_wormhole.config.key(), false),
+            AccountMeta::new(bridge_to_wormhole.user_token_account.key(), false),
+            AccountMeta::new(bridge_to_wormhole.mint.key(), false),
+            AccountMeta::new(bridge_to_wormhole.custody_token.key(), false),
+            AccountMeta::new_readonly(bridge_to_wormhole.transfer_authority.key(), false),
+            AccountMeta::new_readonly(bridge_to_wormhole.custody_authority.key(), false),
+        ]
+    };
+    account_metas.extend_from_slice(&[
         AccountMeta::new(bridge_to_wormhole.core_bridge_config.key(), false),
         AccountMeta::new(bridge_to_wormhole.core_message.key(), true),
         AccountMeta::new_readonly(bridge_to_wormhole.core_emitter.key(), false),
@@ -122,15 +256,16 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: Br
         // Program
         AccountMeta::new_readonly(bridge_to_wormhole.core_bridge_program.key(), false),
         AccountMeta::new_readonly(bridge_to_wormhole.token_program.key(), false),
-    ];
+    ]);
 
     let args_vec: Vec<u8>;
     if bridge_to_wormhole_args.redeemer == [0u8; 32] {
-        let mut _args_vec = &mut vec![LegacyInstruction::TransferTokensNative as u8];
+        let instruction = if wrapped { LegacyInstruction::TransferTokensWrapped } else { LegacyInstruction::TransferTokensNative };
+        let mut _args_vec = &mut vec![instruction as u8];
         let args = TransferTokensArgs {
             nonce: bridge_to_wormhole_args.nonce as u32,
             amount: data.amount,
-            relayer_fee: 0,
+            relayer_fee: bridge_to_wormhole_args.relayer_fee,
             recipient: data.to.try_into().unwrap(),
             recipient_chain: data.to_chain_id as u16,
         };
@@ -138,13 +273,20 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: Br
         args_vec = _args_vec.to_vec();
     } else {
         account_metas.insert(13, AccountMeta::new(bridge_to_wormhole.payer.key(), true));
-        let mut _args_vec = &mut vec![LegacyInstruction::TransferTokensWithPayloadNative as u8];
+        let instruction = if wrapped { LegacyInstruction::TransferTokensWithPayloadWrapped } else { LegacyInstruction::TransferTokensWithPayloadNative };
+        let mut _args_vec = &mut vec![instruction as u8];
+        let payload = if !bridge_to_wormhole_args.payload.is_empty() {
+            bridge_to_wormhole_args.payload.clone()
+        } else {
+            // compatible with current version with no payload
+            data.to
+        };
         let args = TransferTokensWithPayloadArgs {
             nonce: bridge_to_wormhole_args.nonce as u32,
             amount: data.amount,
             redeemer: bridge_to_wormhole_args.redeemer,
             redeemer_chain: data.to_chain_id as u16,
-            payload: data.to,
+            payload,
             cpi_program_id: None,
         };
         _args_vec.append(&mut AnchorSerialize::try_to_vec(&args).unwrap());
@@ -184,31 +326,155 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: Br
     )?;
 
     Ok(BridgeResult{
-        ext: "".to_string(),
+        ext: relayer_fee_log(&ctx.accounts.mint.key(), &bridge_to_wormhole_args),
     })
 
 }
 
+// delivered by the Token Bridge Relayer program, which pulls the tokens from the user token account,
+// charges its registered relayer fee and redeems to data.to on the destination chain.
+// The registered fee is priced by the relayer on the destination chain, so no RelayerFee is logged.
+fn relay_handler<'info>(ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>, data: BridgeToArgs, bridge_to_wormhole_args: BridgeToWromholeArgs) -> Result<BridgeResult> {
+    let mint = ctx.accounts.mint.key();
+    let relayer_accounts = &ctx.remaining_accounts[1..];
+    // the wrapped transfer accounts carry the wrapped_meta of the mint
+    let wrapped = relayer_accounts.iter().any(|account| is_wrapped_mint(account, &mint));
+
+    let args = TransferWithRelayArgs {
+        amount: data.amount,
+        to_native_token_amount: 0,
+        recipient_chain: data.to_chain_id as u16,
+        recipient_address: safe_to_fixed_bytes(data.to)?,
+        batch_id: bridge_to_wormhole_args.nonce as u32,
+    };
+    let mut new_data = if wrapped {
+        TransferWithRelayArgs::WRAPPED_INSTRUCTION_BYTES.to_vec()
+    } else {
+        TransferWithRelayArgs::NATIVE_INSTRUCTION_BYTES.to_vec()
+    };
+    new_data.extend_from_slice(&args.try_to_vec()?);
+    if !wrapped {
+        // wrap_native, the relayer wraps the native sol of a BRIDGE itself
+        new_data.push((data.swap_type == SwapType::BRIDGE && mint == wrapped_sol::ID) as u8);
+    }
+
+    let mut account_metas = vec![];
+    let mut account_infos = vec![];
+    // the relayer transfer accounts are passed through as given
+    for account in relayer_accounts {
+        account_metas.push(AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        });
+        account_infos.push(account.to_account_info());
+    }
+    account_infos.push(ctx.remaining_accounts[0].to_account_info());
+
+    let ix = Instruction {
+        program_id: TokenBridgeRelayer::id(),
+        data: new_data,
+        accounts: account_metas,
+    };
+
+    invoke(
+        &ix,
+        &account_infos
+    )?;
+
+    Ok(BridgeResult{
+        ext: String::new(),
+    })
+}
+
+// the relayer fee of a token bridge transfer is paid on the destination chain to whoever redeems it,
+// log it for the relayer
+fn relayer_fee_log(mint: &Pubkey, args: &BridgeToWromholeArgs) -> String {
+    if args.relayer_fee == 0 {
+        return String::new();
+    }
+    let relayer_fee = RelayerFee {
+        amount: args.relayer_fee,
+        mint: mint.to_string(),
+        to: String::new(),
+    };
+    serde_json::to_string(&relayer_fee).unwrap()
+}
+
+/// Delivery of a wormhole transfer.
+#[derive(Debug, PartialEq, AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub enum WormholeMode {
+    TOKENBRIDGE, // token bridge transfer, redeemed on the destination chain by its redeemer or a relayer paid relayer_fee
+    RELAYER,     // Token Bridge Relayer transfer, redeemed by the relayer for its registered fee
+}
+
 #[derive(Debug, AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct BridgeToWromholeArgs {
     pub nonce: u64,
     pub redeemer: [u8; 32],
+    pub mode: WormholeMode,
+    pub relayer_fee: u64,
     pub payload: Vec<u8>,
 }
 
 impl BridgeToWromholeArgs {
+    // nonce (8, be) | redeemer (32) | mode (1, optional)
+    // TOKENBRIDGE: | relayer_fee (8, be) | payload (optional)
+    // RELAYER: nothing follows, the redeemer is unused
     pub fn try_from_vec(data: &[u8]) -> Result<BridgeToWromholeArgs> {
+        require!(data.len() >= 40, XBridgeErrorCode::DeserializationError);
         let nonce = u64::from_be_bytes(<[u8; 8]>::try_from(&data[0..8]).unwrap());
         let redeemer = <[u8; 32]>::try_from(&data[8..40]).unwrap();
-        let payload = data[40..].to_vec();
+        // compatible with current version with no mode
+        if data.len() == 40 {
+            return Ok(BridgeToWromholeArgs{
+                nonce,
+                redeemer,
+                mode: WormholeMode::TOKENBRIDGE,
+                relayer_fee: 0,
+                payload: vec![],
+            });
+        }
+        let mode = WormholeMode::try_from_slice(&data[40..41]).map_err(|_| XBridgeErrorCode::DeserializationError)?;
+        if mode == WormholeMode::RELAYER {
+            require!(data.len() == 41, XBridgeErrorCode::DeserializationError);
+            return Ok(BridgeToWromholeArgs{
+                nonce,
+                redeemer,
+                mode,
+                relayer_fee: 0,
+                payload: vec![],
+            });
+        }
+        require!(data.len() >= 49, XBridgeErrorCode::DeserializationError);
+        let relayer_fee = u64::from_be_bytes(<[u8; 8]>::try_from(&data[41..49]).unwrap());
+        let payload = data[49..].to_vec();
         Ok(BridgeToWromholeArgs{
             nonce,
             redeemer,
+            mode,
+            relayer_fee,
             payload,
         })
     }
 }
 
+// Token Bridge Relayer transfer_native_tokens_with_relay / transfer_wrapped_tokens_with_relay args,
+// the native transfer is followed by wrap_native (bool)
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TransferWithRelayArgs {
+    pub amount: u64,
+    pub to_native_token_amount: u64,
+    pub recipient_chain: u16,
+    pub recipient_address: [u8; 32],
+    pub batch_id: u32,
+}
+
+impl TransferWithRelayArgs {
+    pub const NATIVE_INSTRUCTION_BYTES: [u8; 8] = [70, 101, 60, 125, 91, 218, 58, 204];
+    pub const WRAPPED_INSTRUCTION_BYTES: [u8; 8] = [25, 63, 69, 217, 250, 9, 127, 122];
+}
+
 #[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct TransferTokensArgs {
     pub nonce: u32,
@@ -305,7 +571,8 @@ pub struct BridgeToWormhole<'info> {
         bump, 
         seeds::program = token_bridge_program.key(),
     )]
-    /// CHECK: Custody Token Account (mut, seeds = \[mint.key\], seeds::program = token_bridge_program).
+    /// CHECK: Custody Token Account (mut, seeds = \[mint.key\], seeds::program = token_bridge_program),
+    /// or Wrapped Meta (read-only, seeds = \["meta", mint.key\]) when the mint is a wormhole wrapped mint.
     pub custody_token: AccountInfo<'info>,
     
     #[account(
@@ -428,6 +695,7 @@ pub struct CrossChainDataEvent{
 #[cfg(test)]
 mod test {
     use super::*;
+    use anchor_lang::solana_program::hash as hash;
 
     #[test]
     fn decode_data(){
@@ -459,6 +727,8 @@ mod test {
         let data = BridgeToWromholeArgs{
             nonce: 0x2766c,
             redeemer: [0; 32],
+            mode: WormholeMode::TOKENBRIDGE,
+            relayer_fee: 0,
             payload: vec![],
         };
 
@@ -470,4 +740,79 @@ mod test {
         // msg!("res: {:?}", vec_to_hex_string(BridgeToWromholeArgs::try_from_vec(&data2).unwrap().redeemer.to_vec()));
     }
 
+    #[test]
+    fn decode_wormhole_args_with_relayer_fee() {
+        let mut data = vec![0u8; 40];
+        data[7] = 1;
+        data.push(0);
+        data.extend_from_slice(&1000u64.to_be_bytes());
+        data.extend_from_slice(&[0xab, 0xcd]);
+        let args = BridgeToWromholeArgs::try_from_vec(&data).unwrap();
+        assert_eq!(args.nonce, 1);
+        assert_eq!(args.mode, WormholeMode::TOKENBRIDGE);
+        assert_eq!(args.relayer_fee, 1000);
+        assert_eq!(args.payload, vec![0xab, 0xcd]);
+
+        let legacy = BridgeToWromholeArgs::try_from_vec(&data[..40]).unwrap();
+        assert_eq!(legacy.mode, WormholeMode::TOKENBRIDGE);
+        assert_eq!(legacy.relayer_fee, 0);
+        assert!(legacy.payload.is_empty());
+        assert!(BridgeToWromholeArgs::try_from_vec(&data[..45]).is_err());
+    }
+
+    #[test]
+    fn relayed_wormhole_args() {
+        let mut data = vec![0u8; 40];
+        data.push(1);
+        let args = BridgeToWromholeArgs::try_from_vec(&data).unwrap();
+        assert_eq!(args.mode, WormholeMode::RELAYER);
+        assert_eq!(args.relayer_fee, 0);
+        // the relayer charges its registered fee, no relayer_fee or payload follows
+        data.extend_from_slice(&1000u64.to_be_bytes());
+        assert!(BridgeToWromholeArgs::try_from_vec(&data).is_err());
+        data.truncate(40);
+        data.push(2);
+        assert!(BridgeToWromholeArgs::try_from_vec(&data).is_err());
+    }
+
+    #[test]
+    fn relayer_fee_recipient() {
+        let args = BridgeToWromholeArgs {
+            nonce: 1,
+            redeemer: [0; 32],
+            mode: WormholeMode::TOKENBRIDGE,
+            relayer_fee: 1000,
+            payload: vec![],
+        };
+        let log: RelayerFee = serde_json::from_str(&relayer_fee_log(&wrapped_sol::ID, &args)).unwrap();
+        assert_eq!(log.amount, 1000);
+        assert_eq!(log.mint, wrapped_sol::ID.to_string());
+        assert!(log.to.is_empty());
+    }
+
+    #[test]
+    fn relay_sighash() {
+        let mut sighash = [0u8; 8];
+        sighash.copy_from_slice(&hash::hash(b"global:transfer_native_tokens_with_relay").to_bytes()[..8]);
+        assert_eq!(sighash, TransferWithRelayArgs::NATIVE_INSTRUCTION_BYTES);
+        sighash.copy_from_slice(&hash::hash(b"global:transfer_wrapped_tokens_with_relay").to_bytes()[..8]);
+        assert_eq!(sighash, TransferWithRelayArgs::WRAPPED_INSTRUCTION_BYTES);
+    }
+
+    #[test]
+    fn encode_relay_args() {
+        let args = TransferWithRelayArgs {
+            amount: 1_000_000,
+            to_native_token_amount: 0,
+            recipient_chain: 2,
+            recipient_address: [7; 32],
+            batch_id: 9,
+        };
+        let encoded = args.try_to_vec().unwrap();
+        assert_eq!(encoded.len(), 8 + 8 + 2 + 32 + 4);
+        assert_eq!(&encoded[16..18], &2u16.to_le_bytes());
+        assert_eq!(&encoded[18..50], &[7u8; 32]);
+        assert_eq!(&encoded[50..], &9u32.to_le_bytes());
+    }
+
 }
\ No newline at end of file
@@ -43,4 +43,16 @@ pub struct LogBridgeToVersion1Event {
     pub from: String,        // solana链上发起用户的地址
     pub user_address: String,     // toswap接受钱包地址
     pub ext: String,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct CommissionPaid {
+    pub order_id: String,    // same as LogBridgeToVersion1Event.order_id, or the claim orderid
+    pub adaptor_id: Option<u8>, // None for bridge_in claims
+    pub partner_id: u64,
+    pub recipient: Pubkey,   // commission token account, or SOL commission account
+    pub token: Pubkey,       // commission mint, Pubkey::default() for SOL
+    pub rate: u16,
+    pub base_amount: u64,    // amount the rate is applied to
+    pub amount: u64,
+}
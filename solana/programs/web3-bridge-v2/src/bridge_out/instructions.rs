@@ -1,9 +1,17 @@
 pub mod bridge_to;
 pub mod bridge_to_log;
 pub mod bridge_to_log_commission;
+pub mod set_chain;
+pub mod set_ntt_manager;
+pub mod set_oft;
+pub mod set_partner;
 
 pub use {
     bridge_to::*,
     bridge_to_log::*,
     bridge_to_log_commission::*,
+    set_chain::*,
+    set_ntt_manager::*,
+    set_oft::*,
+    set_partner::*,
 };
\ No newline at end of file
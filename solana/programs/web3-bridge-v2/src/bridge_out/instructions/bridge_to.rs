@@ -1,9 +1,10 @@
 use {
-    crate::
+    crate::{
         bridge_out::{
-            AdaptorID, SwapType
-        }
-    , anchor_lang::prelude::*, anchor_spl::{
+            AdaptorID, ChainRegistry, SwapType
+        },
+        common::XBridgeErrorCode,
+    }, anchor_lang::prelude::*, anchor_spl::{
         associated_token::AssociatedToken,
         token::{Mint, Token, TokenAccount},
         token_2022::Token2022,
@@ -11,6 +12,7 @@ use {
 };
 
 #[derive(Accounts)]
+#[instruction(data: BridgeToArgs)]
 pub struct BridgeTo<'info> {
     #[account(mut)]
     /// Payer.
@@ -56,6 +58,16 @@ pub struct BridgeTo<'info> {
     /// CHECK: fixed
     #[account(address = System::id())]
     pub system_program: AccountInfo<'info>,
+
+    #[account(
+        constraint = chain_registry.enabled @ XBridgeErrorCode::ChainNotEnabled,
+        seeds = [b"chain", [data.adaptor_id.0].as_ref(), data.to_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// Chain Registry.
+    /// Maps data.to_chain_id of data.adaptor_id to the logged chain id, set by set_chain.
+    /// Optional for adaptors whose to_chain_id is already the logged chain id, see BridgeAdaptor::CHAIN_REGISTRY_REQUIRED.
+    pub chain_registry: Option<Box<Account<'info, ChainRegistry>>>,
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
@@ -1,8 +1,12 @@
 use {
     crate::{
         bridge_out::{
-            allbridge, cctp, debridgedln, meson, wormhole, wanchain, mayan_swift, bridgers,
-            AdaptorID, BridgeTo, BridgeToArgs, BridgeToArgsExtData, 
+            across::AcrossAdaptor, allbridge::AllbridgeAdaptor, bridgers::BridgersAdaptor, cctp::CctpAdaptor,
+            cctp_v2::CctpV2Adaptor, debridgedln::DebridgedlnAdaptor, layerzero_oft::OftAdaptor,
+            mayan_mctp::MayanMctpAdaptor, mayan_swift::MayanSwiftAdaptor, mayan_wh_swap::MayanWhSwapAdaptor,
+            meson::MesonAdaptor, ntt::NttAdaptor,
+            wanchain::WanchainAdaptor, wormhole::WormholeAdaptor,
+            BridgeAdaptor, BridgeTo, BridgeToArgs, BridgeToArgsExtData, 
             LogBridgeToVersion1, LogBridgeToVersion1Event, SwapType
         },
         common::{vec_to_hex_string, XBridgeErrorCode, wrapped_sol},
@@ -14,15 +18,59 @@ pub fn bridge_to_log<'info>(
     ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
     data: BridgeToArgs,
 ) -> Result<()> {
+    bridge_to_log_consumed(ctx, data)?;
+    Ok(())
+}
+
+// bridges and logs, returns the amount consumed from the user as measured around the adaptor call
+pub fn bridge_to_log_consumed<'info>(
+    ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    data: BridgeToArgs,
+) -> Result<u64> {
+    // adaptor registration table
+    match data.adaptor_id {
+        BridgersAdaptor::ID => bridge_to_log_with::<BridgersAdaptor>(ctx, data),
+        WanchainAdaptor::ID => bridge_to_log_with::<WanchainAdaptor>(ctx, data),
+        CctpAdaptor::ID => bridge_to_log_with::<CctpAdaptor>(ctx, data),
+        WormholeAdaptor::ID => bridge_to_log_with::<WormholeAdaptor>(ctx, data),
+        MesonAdaptor::ID => bridge_to_log_with::<MesonAdaptor>(ctx, data),
+        DebridgedlnAdaptor::ID => bridge_to_log_with::<DebridgedlnAdaptor>(ctx, data),
+        AllbridgeAdaptor::ID => bridge_to_log_with::<AllbridgeAdaptor>(ctx, data),
+        MayanSwiftAdaptor::ID => bridge_to_log_with::<MayanSwiftAdaptor>(ctx, data),
+        CctpV2Adaptor::ID => bridge_to_log_with::<CctpV2Adaptor>(ctx, data),
+        NttAdaptor::ID => bridge_to_log_with::<NttAdaptor>(ctx, data),
+        OftAdaptor::ID => bridge_to_log_with::<OftAdaptor>(ctx, data),
+        AcrossAdaptor::ID => bridge_to_log_with::<AcrossAdaptor>(ctx, data),
+        MayanMctpAdaptor::ID => bridge_to_log_with::<MayanMctpAdaptor>(ctx, data),
+        MayanWhSwapAdaptor::ID => bridge_to_log_with::<MayanWhSwapAdaptor>(ctx, data),
+        _ => Err(XBridgeErrorCode::InvalidAdaptorId.into()),
+    }
+}
+
+fn bridge_to_log_with<'info, A: BridgeAdaptor>(
+    ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
+    data: BridgeToArgs,
+) -> Result<u64> {
+    let args = A::decode_args(&data)?;
+    A::validate_accounts(&ctx, &data, &args)?;
+
+    // the registry maps the bridge's own chain id to the logged chain id
     let mut data_clone = data.clone();
+    match &ctx.accounts.chain_registry {
+        Some(chain_registry) => {
+            chain_registry.validate_to(&data.to)?;
+            data_clone.to_chain_id = chain_registry.chain_id;
+        }
+        None => require!(!A::CHAIN_REGISTRY_REQUIRED, XBridgeErrorCode::ChainRegistryRequired),
+    }
     let payer = ctx.accounts.payer.clone();
     let mint = ctx.accounts.mint.clone(); 
     let mut user_token_account_clone = ctx.accounts.user_token_account.clone();
     let from_bridge_token = mint.key().clone();
     let payer_address = payer.key().clone();
-    let res;
     let before_balance: u64;
     let after_balance: u64;
+    let consumed_amount: u64;
     let swap_type = data.swap_type.clone();
 
     // @dev XBridge only support native sol to consume.
@@ -55,87 +103,13 @@ pub fn bridge_to_log<'info>(
         }
     }
 
-    match data.adaptor_id {
-        AdaptorID::Meson => {
-            let bridge_to_meson_args = meson::BridgeToMesonArgs::try_from_vec(&data.data).unwrap();
-            res = meson::handler(ctx, data, bridge_to_meson_args).unwrap();
-        }
-        AdaptorID::Wormhole => {
-            let bridge_to_wormhole_args = wormhole::BridgeToWromholeArgs::try_from_vec(&data.data).unwrap();
-            res = wormhole::handler(ctx, data, bridge_to_wormhole_args).unwrap();
-            data_clone.to_chain_id = match data_clone.to_chain_id {
-                2 => 1,
-                4 => 56,
-                5 => 137,
-                6 => 43114,
-                10 => 250,
-                13 => 8217,
-                14 => 42220,
-                16 => 1284,
-                23 => 42161,
-                24 => 10,
-                _ => data_clone.to_chain_id,
-            };
-        }
-        AdaptorID::Debridgedln => {
-            let bridge_to_debridgedln_args = debridgedln::BridgeToDebridgedlnArgs::try_from_vec(&data.data).unwrap();
-            res = debridgedln::handler(ctx, data, bridge_to_debridgedln_args).unwrap();
-        }
-        AdaptorID::Cctp => {
-            res = cctp::handler(ctx, data).unwrap();
-            data_clone.to_chain_id = match data_clone.to_chain_id {
-                0 => 1,
-                1 => 43114,
-                2 => 10,
-                3 => 42161,
-                6 => 8453,
-                7 => 137,
-                8 => 784,
-                _ => data_clone.to_chain_id,
-            };
-        }
-        AdaptorID::Allbridge => {
-            let bridge_to_allbridge_args = allbridge::BridgeToAllbridgeArgs::try_from_vec(&data.data).unwrap();
-            res = allbridge::handler(ctx, data, bridge_to_allbridge_args).unwrap();
-            data_clone.to_chain_id = match data_clone.to_chain_id {
-                2 => 56,
-                5 => 137,
-                6 => 42161,
-                8 => 43114,
-                9 => 8453,
-                11 => 42220,
-                _ => data_clone.to_chain_id,
-            };
-            
-            // The actual amount deducted for the allbridge bridge is not equal to the amount entered, due to the mechanism of the bridge
-            user_token_account_clone.reload()?;
-            data_clone.amount = before_balance.saturating_sub(user_token_account_clone.amount);
+    let bridge_result = A::execute(ctx, data, args)?;
+    let ext = bridge_result.ext;
 
-        }
-        AdaptorID::Wanchain => {
-            let bridge_to_wanchain_args = wanchain::BridgeToWanchainArgs::try_from_vec(&data.data).unwrap();
-            res = wanchain::handler(ctx, data, bridge_to_wanchain_args).unwrap();
-        }
-        AdaptorID::MayanSwift => {
-            let bridge_to_mayan_swift_args = mayan_swift::BridgeToMayanSwiftArgs::try_from_vec(&data.data).unwrap();
-            res = mayan_swift::handler(ctx, data, bridge_to_mayan_swift_args).unwrap();
-            data_clone.to_chain_id = match data_clone.to_chain_id {
-                2 => 1,
-                4 => 56,
-                5 => 137,
-                6 => 43114,
-                23 => 42161,
-                24 => 10,
-                30 => 8453,
-                _ => return Err(XBridgeErrorCode::InvalidToChainId.into()),
-            };
-        }
-        AdaptorID::Bridgers => {
-            let bridge_to_bridgers_args = bridgers::BridgeToBridgersArgs::try_from_vec(&data.data).unwrap();
-            res = bridgers::handler(ctx, data, bridge_to_bridgers_args).unwrap();
-        }
-        _ => return Err(XBridgeErrorCode::InvalidAdaptorId.into()),
-    }   
+    if A::MEASURE_AMOUNT {
+        user_token_account_clone.reload()?;
+        data_clone.amount = before_balance.saturating_sub(user_token_account_clone.amount);
+    }
 
     match swap_type {
         SwapType::BRIDGE => {
@@ -154,16 +128,20 @@ pub fn bridge_to_log<'info>(
                     before_balance >= after_balance + data_clone.amount,
                     XBridgeErrorCode::AmountMustEqualConsumed
                 );
+                // lamports also pay the transaction rent, the bridged amount is what was consumed
+                consumed_amount = data_clone.amount;
             } else {
                 user_token_account_clone.reload()?;
                 after_balance = user_token_account_clone.amount;
                 msg!("before_balance (Token): {}", before_balance);
                 msg!("after_balance (Token): {}", after_balance);
                 msg!("data_clone.amount: {}", data_clone.amount);
+                let expected_amount = A::expected_consumed(&mint.key(), data_clone.amount)?;
                 require!(
-                    before_balance == after_balance + data_clone.amount,
+                    before_balance == after_balance + expected_amount,
                     XBridgeErrorCode::AmountMustEqualConsumed
                 );
+                consumed_amount = expected_amount;
             }
         },
         SwapType::SWAPANDBRIDGE => {
@@ -176,23 +154,12 @@ pub fn bridge_to_log<'info>(
             msg!("before_balance (Token): {}", before_balance);
             msg!("after_balance (Token): {}", after_balance);
             msg!("data_clone.amount: {}", data_clone.amount);
-            if data_clone.adaptor_id == AdaptorID::Wormhole && mint.key() == wrapped_sol::ID {
-                // Special handling is required because Wormhole rounds down decimal places when transferring WSOL across chains
-                let adjusted_amount = data_clone.amount
-                    .checked_div(10)
-                    .ok_or(XBridgeErrorCode::CalculationError)?
-                    .checked_mul(10)
-                    .ok_or(XBridgeErrorCode::CalculationError)?;
-                require!(
-                    before_balance == after_balance + adjusted_amount,
-                    XBridgeErrorCode::AmountMustEqualConsumed
-                );
-            } else {
-                require!(
-                    before_balance == after_balance + data_clone.amount,
-                    XBridgeErrorCode::AmountMustEqualConsumed
-                );
-            }
+            let expected_amount = A::expected_consumed(&mint.key(), data_clone.amount)?;
+            require!(
+                before_balance == after_balance + expected_amount,
+                XBridgeErrorCode::AmountMustEqualConsumed
+            );
+            consumed_amount = expected_amount;
         }
     }
 
@@ -208,7 +175,7 @@ pub fn bridge_to_log<'info>(
 
     emit!(LogBridgeToVersion1Event {
         order_id: data_clone.order_id.to_string(),
-        adaptor_id: data_clone.adaptor_id as u8,
+        adaptor_id: data_clone.adaptor_id.0,
         to: to_hex_string.clone(),
         amount: data_clone.amount,
         swap_type: data_clone.swap_type as u8,
@@ -217,12 +184,12 @@ pub fn bridge_to_log<'info>(
         src_chain_id: 501,
         from: payer_address.to_string(),
         user_address: user_address_str.clone(),
-        ext:res.ext.clone()
+        ext: ext.clone()
     });
 
     let xbridge_log: LogBridgeToVersion1 = LogBridgeToVersion1 {
         order_id: data_clone.order_id.to_string(),
-        adaptor_id: data_clone.adaptor_id as u8,
+        adaptor_id: data_clone.adaptor_id.0,
         to: to_hex_string.clone(),
         amount: data_clone.amount,
         swap_type: data_clone.swap_type as u8,
@@ -231,9 +198,9 @@ pub fn bridge_to_log<'info>(
         src_chain_id: 501,
         from: payer_address.to_string(),
         user_address: user_address_str.clone(),
-        ext:res.ext.clone()
+        ext: ext.clone()
     };
     msg!("LogBridgeToVersion1:{}", serde_json::to_string(&xbridge_log).unwrap());
 
-    Ok(())
+    Ok(consumed_amount)
 }
@@ -1,10 +1,10 @@
 use {
     crate::{
         bridge_out::{
-             instructions::bridge_to::BridgeToBumps, AdaptorID, BridgeTo, BridgeToArgs, SwapType
+             instructions::bridge_to::BridgeToBumps, AdaptorID, BridgeTo, BridgeToArgs, ChainRegistry, CommissionMode, CommissionPaid, PartnerConfig, SwapType
         },
-        bridge_to_log::bridge_to_log,
-        common::{XBridgeErrorCode, COMMISSION_DENOMINATOR, COMMISSION_RATE_LIMIT},
+        bridge_to_log::bridge_to_log_consumed,
+        common::{wrapped_sol, XBridgeErrorCode},
     }, 
     anchor_lang::{
         prelude::*, 
@@ -20,6 +20,7 @@ use {
 
 
 #[derive(Accounts)]
+#[instruction(data: BridgeToCommissionArgs)]
 pub struct BridgeToSplCommission<'info> {
     #[account(mut)]
     /// Payer.
@@ -39,13 +40,15 @@ pub struct BridgeToSplCommission<'info> {
     pub user_token_account: Account<'info, TokenAccount>,
 
     #[account(
-        mut,
-        token::mint = mint,
+        constraint = partner_config.active @ XBridgeErrorCode::PartnerNotActive,
+        seeds = [b"partner", data.partner_id.to_le_bytes().as_ref()],
+        bump
     )]
-    /// Commission Token Account.
-    /// ATA: mint = mint
-    /// This account is used to pay for commission token
-    pub commission_token_account: Account<'info, TokenAccount>,
+    /// Partner Config.
+    /// The on-chain commission recipients of data.partner_id.
+    /// remaining_accounts start with one commission account per recipient, in order,
+    /// followed by the adaptor accounts.
+    pub partner_config: Account<'info, PartnerConfig>,
 
     #[account(mut)]
     /// Mint.
@@ -74,6 +77,24 @@ pub struct BridgeToSplCommission<'info> {
     /// CHECK: fixed
     #[account(address = System::id())]
     pub system_program: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = commission_token_account.owner == payer.key() @ XBridgeErrorCode::InvalidCommissionTokenAccount
+    )]
+    /// Commission Token Account.
+    /// owner = payer, pays the commission in its mint, required by CommissionMode::CONSUMEDINMINT.
+    pub commission_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = chain_registry.enabled @ XBridgeErrorCode::ChainNotEnabled,
+        seeds = [b"chain", [data.adaptor_id.0].as_ref(), data.to_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// Chain Registry.
+    /// Maps data.to_chain_id of data.adaptor_id to the logged chain id, set by set_chain.
+    /// Optional for adaptors whose to_chain_id is already the logged chain id, see BridgeAdaptor::CHAIN_REGISTRY_REQUIRED.
+    pub chain_registry: Option<Box<Account<'info, ChainRegistry>>>,
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
@@ -87,47 +108,40 @@ pub struct BridgeToCommissionArgs {
     pub data: Vec<u8>,         // data for bridge
     pub ext_data: Vec<u8>,     // ext data for extension feature
 
-    pub commission_rate: u16,       // Commission rate
+    pub partner_id: u64,       // partner id, the commission rate and recipient are read from its PartnerConfig
+    pub commission_mode: CommissionMode, // commission on data.amount, or on the amount consumed by the bridge
 }
 
 pub fn bridge_to_log_splcommission<'info>(
-    ctx: Context<'_, '_, '_, 'info, BridgeToSplCommission<'info>>,
+    ctx: Context<'_, '_, 'info, 'info, BridgeToSplCommission<'info>>,
     data: BridgeToCommissionArgs,
 ) -> Result<()> {
-    // commission
-    require!(
-        data.commission_rate > 0 && data.commission_rate <= COMMISSION_RATE_LIMIT,
-        XBridgeErrorCode::InvalidCommissionRate
-    );
+    // commission recipients lead remaining_accounts, rates are bounded by set_partner
+    let recipients_len = ctx.accounts.partner_config.recipients.len();
     require!(
-        ctx.accounts.commission_token_account.mint == ctx.accounts.mint.key(),
+        ctx.remaining_accounts.len() >= recipients_len,
         XBridgeErrorCode::InvalidCommissionTokenAccount
     );
-    let commission_amount = data
-            .amount
-            .checked_mul(data.commission_rate as u64)
-            .ok_or(XBridgeErrorCode::CalculationError)?
-            .checked_div(COMMISSION_DENOMINATOR - data.commission_rate as u64)
-            .ok_or(XBridgeErrorCode::CalculationError)?;
-    // transfer spl commission
-    let cpi_commissionfee = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info(),
-        to: ctx.accounts.commission_token_account.to_account_info(),
-        authority: ctx.accounts.payer.to_account_info(),
+    let commission_mode = data.commission_mode;
+    let commission_data = data.clone();
+    let commission_token_account = match commission_mode {
+        CommissionMode::CONSUMEDINMINT { .. } => ctx
+            .accounts
+            .commission_token_account
+            .clone()
+            .ok_or(XBridgeErrorCode::InvalidCommissionTokenAccount)?,
+        _ => ctx.accounts.user_token_account.clone(),
     };
-    let cpi_ctx_commissionfee = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_commissionfee);
-    token::transfer(cpi_ctx_commissionfee, commission_amount)?;
-    msg!(
-        "commission_to: {:?}, commission_amount: {:?}",
-        ctx.accounts.commission_token_account.key(),
-        commission_amount
-    );
-
+    if commission_mode == CommissionMode::AMOUNT {
+        let commission_amounts = ctx.accounts.partner_config.commission_amounts(data.amount)?;
+        pay_spl_commission(&ctx, &data, &commission_token_account, data.amount, &commission_amounts)?;
+    }
 
     // BridgeTo、BridgeToArgs
     let mut bridge_to_accounts = BridgeTo {
         payer: ctx.accounts.payer.clone(),
         user_token_account: ctx.accounts.user_token_account.clone(),
+        chain_registry: ctx.accounts.chain_registry.clone(),
         mint: ctx.accounts.mint.clone(),
         associated_token_program: ctx.accounts.associated_token_program.clone(),
         token_program: ctx.accounts.token_program.clone(),
@@ -138,7 +152,7 @@ pub fn bridge_to_log_splcommission<'info>(
     let _bridge_to_ctx: Context<'_, '_, '_, '_, BridgeTo<'_>> = Context::new(
         ctx.program_id,
         &mut bridge_to_accounts,
-        ctx.remaining_accounts,
+        &ctx.remaining_accounts[recipients_len..],
         BridgeToBumps::default(),
     );
     
@@ -154,15 +168,68 @@ pub fn bridge_to_log_splcommission<'info>(
     };
 
     // xbirdge
-    bridge_to_log(
+    let consumed_amount = bridge_to_log_consumed(
         _bridge_to_ctx,
         _args,
     )?;
 
+    if let Some((price_in, price_out)) = consumed_price(commission_mode) {
+        let commission_amounts = ctx.accounts.partner_config.consumed_amounts(consumed_amount, price_in, price_out)?;
+        pay_spl_commission(&ctx, &commission_data, &commission_token_account, consumed_amount, &commission_amounts)?;
+    }
+
+    Ok(())
+}
+
+// the price of the commission paid after bridging, None when it is paid before
+fn consumed_price(commission_mode: CommissionMode) -> Option<(u64, u64)> {
+    match commission_mode {
+        CommissionMode::AMOUNT => None,
+        CommissionMode::CONSUMED => Some((1, 1)),
+        CommissionMode::CONSUMEDINMINT { price_in, price_out } => Some((price_in, price_out)),
+    }
+}
+
+// pays every partner recipient its commission on base_amount from the payer's from_token_account
+fn pay_spl_commission<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, BridgeToSplCommission<'info>>,
+    data: &BridgeToCommissionArgs,
+    from_token_account: &Account<'info, TokenAccount>,
+    base_amount: u64,
+    commission_amounts: &[u64],
+) -> Result<()> {
+    let partner_config = &ctx.accounts.partner_config;
+    for (i, recipient) in partner_config.recipients.iter().enumerate() {
+        let commission_token_account: Account<TokenAccount> = Account::try_from(&ctx.remaining_accounts[i])?;
+        require!(
+            commission_token_account.mint == from_token_account.mint
+                && commission_token_account.owner == recipient.recipient,
+            XBridgeErrorCode::InvalidCommissionTokenAccount
+        );
+        // transfer spl commission
+        let cpi_commissionfee = Transfer {
+            from: from_token_account.to_account_info(),
+            to: ctx.remaining_accounts[i].clone(),
+            authority: ctx.accounts.payer.to_account_info(),
+        };
+        let cpi_ctx_commissionfee = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_commissionfee);
+        token::transfer(cpi_ctx_commissionfee, commission_amounts[i])?;
+        emit!(CommissionPaid {
+            order_id: data.order_id.to_string(),
+            adaptor_id: Some(data.adaptor_id.0),
+            partner_id: data.partner_id,
+            recipient: commission_token_account.key(),
+            token: from_token_account.mint,
+            rate: recipient.rate,
+            base_amount,
+            amount: commission_amounts[i],
+        });
+    }
     Ok(())
 }
 
 #[derive(Accounts)]
+#[instruction(data: BridgeToCommissionArgs)]
 pub struct BridgeToSolCommission<'info> {
     #[account(mut)]
     /// Payer.
@@ -181,10 +248,16 @@ pub struct BridgeToSolCommission<'info> {
     /// This account is used to pay for bridge token, owner by payer(user).
     pub user_token_account: Account<'info, TokenAccount>,
 
-    #[account(mut)]
-    /// Commission Account.
-    /// This account is used to pay for commission sol
-    pub commission_account: SystemAccount<'info>,
+    #[account(
+        constraint = partner_config.active @ XBridgeErrorCode::PartnerNotActive,
+        seeds = [b"partner", data.partner_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// Partner Config.
+    /// The on-chain commission recipients of data.partner_id.
+    /// remaining_accounts start with one commission account per recipient, in order,
+    /// followed by the adaptor accounts.
+    pub partner_config: Account<'info, PartnerConfig>,
 
     #[account(mut)]
     /// Mint.
@@ -213,42 +286,47 @@ pub struct BridgeToSolCommission<'info> {
     /// CHECK: fixed
     #[account(address = System::id())]
     pub system_program: AccountInfo<'info>,
+
+    #[account(
+        constraint = chain_registry.enabled @ XBridgeErrorCode::ChainNotEnabled,
+        seeds = [b"chain", [data.adaptor_id.0].as_ref(), data.to_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    /// Chain Registry.
+    /// Maps data.to_chain_id of data.adaptor_id to the logged chain id, set by set_chain.
+    /// Optional for adaptors whose to_chain_id is already the logged chain id, see BridgeAdaptor::CHAIN_REGISTRY_REQUIRED.
+    pub chain_registry: Option<Box<Account<'info, ChainRegistry>>>,
 }
 
 pub fn bridge_to_log_solcommission<'info>(
     ctx: Context<'_, '_, '_, 'info, BridgeToSolCommission<'info>>,
     data: BridgeToCommissionArgs,
 ) -> Result<()> {
-    // commission
+    // the commission is paid in lamports, bridge mint units are lamports only for WSOL,
+    // other mints are converted at the price of CommissionMode::CONSUMEDINMINT
     require!(
-        data.commission_rate > 0 && data.commission_rate <= COMMISSION_RATE_LIMIT,
-        XBridgeErrorCode::InvalidCommissionRate
+        ctx.accounts.mint.key() == wrapped_sol::ID
+            || matches!(data.commission_mode, CommissionMode::CONSUMEDINMINT { .. }),
+        XBridgeErrorCode::InvalidCommissionMint
     );
-    let commission_amount = data
-            .amount
-            .checked_mul(data.commission_rate as u64)
-            .ok_or(XBridgeErrorCode::CalculationError)?
-            .checked_div(COMMISSION_DENOMINATOR - data.commission_rate as u64)
-            .ok_or(XBridgeErrorCode::CalculationError)?;
-    // transfer sol commission
-    invoke(
-        &transfer(&ctx.accounts.payer.key(), &ctx.accounts.commission_account.key(), commission_amount),
-        &[
-            ctx.accounts.payer.to_account_info(),
-            ctx.accounts.commission_account.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ]
-    )?;
-    msg!(
-        "commission_to: {:?}, commission_amount: {:?}",
-        ctx.accounts.commission_account.key(),
-        commission_amount
+    // commission recipients lead remaining_accounts, rates are bounded by set_partner
+    let recipients_len = ctx.accounts.partner_config.recipients.len();
+    require!(
+        ctx.remaining_accounts.len() >= recipients_len,
+        XBridgeErrorCode::InvalidCommissionAccount
     );
+    let commission_mode = data.commission_mode;
+    let commission_data = data.clone();
+    if commission_mode == CommissionMode::AMOUNT {
+        let commission_amounts = ctx.accounts.partner_config.commission_amounts(data.amount)?;
+        pay_sol_commission(&ctx, &data, data.amount, &commission_amounts)?;
+    }
 
     // BridgeTo、BridgeToArgs
     let mut bridge_to_accounts = BridgeTo {
         payer: ctx.accounts.payer.clone(),
         user_token_account: ctx.accounts.user_token_account.clone(),
+        chain_registry: ctx.accounts.chain_registry.clone(),
         mint: ctx.accounts.mint.clone(),
         associated_token_program: ctx.accounts.associated_token_program.clone(),
         token_program: ctx.accounts.token_program.clone(),
@@ -259,7 +337,7 @@ pub fn bridge_to_log_solcommission<'info>(
     let _bridge_to_ctx: Context<'_, '_, '_, '_, BridgeTo<'_>> = Context::new(
         ctx.program_id,
         &mut bridge_to_accounts,
-        ctx.remaining_accounts,
+        &ctx.remaining_accounts[recipients_len..],
         BridgeToBumps::default(),
     );
     
@@ -275,10 +353,52 @@ pub fn bridge_to_log_solcommission<'info>(
     };
 
     // xbirdge
-    bridge_to_log(
+    let consumed_amount = bridge_to_log_consumed(
         _bridge_to_ctx,
         _args,
     )?;
 
+    if let Some((price_in, price_out)) = consumed_price(commission_mode) {
+        let commission_amounts = ctx.accounts.partner_config.consumed_amounts(consumed_amount, price_in, price_out)?;
+        pay_sol_commission(&ctx, &commission_data, consumed_amount, &commission_amounts)?;
+    }
+
+    Ok(())
+}
+
+// pays every partner recipient its commission on base_amount in lamports
+fn pay_sol_commission<'info>(
+    ctx: &Context<'_, '_, '_, 'info, BridgeToSolCommission<'info>>,
+    data: &BridgeToCommissionArgs,
+    base_amount: u64,
+    commission_amounts: &[u64],
+) -> Result<()> {
+    let partner_config = &ctx.accounts.partner_config;
+    for (i, recipient) in partner_config.recipients.iter().enumerate() {
+        let commission_account = &ctx.remaining_accounts[i];
+        require!(
+            commission_account.key() == recipient.recipient && commission_account.is_writable,
+            XBridgeErrorCode::InvalidCommissionAccount
+        );
+        // transfer sol commission
+        invoke(
+            &transfer(&ctx.accounts.payer.key(), &commission_account.key(), commission_amounts[i]),
+            &[
+                ctx.accounts.payer.to_account_info(),
+                commission_account.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ]
+        )?;
+        emit!(CommissionPaid {
+            order_id: data.order_id.to_string(),
+            adaptor_id: Some(data.adaptor_id.0),
+            partner_id: data.partner_id,
+            recipient: commission_account.key(),
+            token: Pubkey::default(),
+            rate: recipient.rate,
+            base_amount,
+            amount: commission_amounts[i],
+        });
+    }
     Ok(())
 }
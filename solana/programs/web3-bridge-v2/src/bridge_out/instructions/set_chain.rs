@@ -0,0 +1,70 @@
+use {
+    crate::{
+        bridge_in::ContractConfig,
+        bridge_out::{AdaptorID, ChainRegistry},
+        common::{XBridgeErrorCode, MAX_RECIPIENT_LEN},
+    },
+    anchor_lang::prelude::*,
+};
+
+
+#[derive(Accounts)]
+#[instruction(data: SetChainParams)]
+pub struct SetChainContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    // chainRegistry state account, one per (adaptor, native chain id)
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ChainRegistry::INIT_SPACE,
+        seeds = [b"chain", [data.adaptor_id.0].as_ref(), data.native_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub chain_registry: Account<'info, ChainRegistry>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SetChainParams {
+    pub adaptor_id: AdaptorID,
+    pub native_chain_id: u64,
+    pub chain_id: u64,
+    pub enabled: bool,
+    pub to_len: u8,
+}
+
+pub fn set_chain(ctx: Context<SetChainContext>, data: SetChainParams) -> Result<()> {
+    require!(data.chain_id != 0, XBridgeErrorCode::InvalidToChainId);
+    require!(data.to_len <= MAX_RECIPIENT_LEN, XBridgeErrorCode::InvalidRecipientLength);
+
+    let chain_registry = &mut ctx.accounts.chain_registry;
+    chain_registry.adaptor_id = data.adaptor_id.0;
+    chain_registry.native_chain_id = data.native_chain_id;
+    chain_registry.chain_id = data.chain_id;
+    chain_registry.enabled = data.enabled;
+    chain_registry.to_len = data.to_len;
+
+    msg!(
+        "Chain {} of adaptor {} updated by owner: {}. Chain id: {}, Recipient length: {}, Enabled: {}",
+        data.native_chain_id,
+        data.adaptor_id.0,
+        ctx.accounts.owner.key(),
+        data.chain_id,
+        data.to_len,
+        data.enabled
+    );
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use {
+    crate::{
+        bridge_in::ContractConfig,
+        bridge_out::{NttManagerRegistry, NTT_MODE_BURNING, NTT_MODE_LOCKING},
+        common::XBridgeErrorCode,
+    },
+    anchor_lang::prelude::*,
+};
+
+
+#[derive(Accounts)]
+#[instruction(data: SetNttManagerParams)]
+pub struct SetNttManagerContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    // nttManagerRegistry state account, one per mint
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + NttManagerRegistry::INIT_SPACE,
+        seeds = [b"ntt_manager", data.mint.as_ref()],
+        bump
+    )]
+    pub ntt_manager_registry: Account<'info, NttManagerRegistry>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SetNttManagerParams {
+    pub mint: Pubkey,
+    pub manager: Pubkey,
+    pub transceiver: Pubkey,
+    pub mode: u8,
+    pub enabled: bool,
+}
+
+pub fn set_ntt_manager(ctx: Context<SetNttManagerContext>, data: SetNttManagerParams) -> Result<()> {
+    require!(
+        data.mode == NTT_MODE_LOCKING || data.mode == NTT_MODE_BURNING,
+        XBridgeErrorCode::InvalidNttMode
+    );
+    let ntt_manager_registry = &mut ctx.accounts.ntt_manager_registry;
+    ntt_manager_registry.mint = data.mint;
+    ntt_manager_registry.manager = data.manager;
+    ntt_manager_registry.transceiver = data.transceiver;
+    ntt_manager_registry.mode = data.mode;
+    ntt_manager_registry.enabled = data.enabled;
+
+    msg!(
+        "NTT manager of mint {} updated by owner: {}. Manager: {}, Transceiver: {}, Mode: {}, Enabled: {}",
+        data.mint,
+        ctx.accounts.owner.key(),
+        data.manager,
+        data.transceiver,
+        data.mode,
+        data.enabled
+    );
+    Ok(())
+}
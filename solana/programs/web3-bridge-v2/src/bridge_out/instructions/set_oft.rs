@@ -0,0 +1,60 @@
+use {
+    crate::{
+        bridge_in::ContractConfig,
+        bridge_out::OftRegistry,
+    },
+    anchor_lang::prelude::*,
+};
+
+
+#[derive(Accounts)]
+#[instruction(data: SetOftParams)]
+pub struct SetOftContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    // oftRegistry state account, one per mint
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + OftRegistry::INIT_SPACE,
+        seeds = [b"oft", data.mint.as_ref()],
+        bump
+    )]
+    pub oft_registry: Account<'info, OftRegistry>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SetOftParams {
+    pub mint: Pubkey,
+    pub oft_program: Pubkey,
+    pub enabled: bool,
+}
+
+pub fn set_oft(ctx: Context<SetOftContext>, data: SetOftParams) -> Result<()> {
+    let oft_registry = &mut ctx.accounts.oft_registry;
+    oft_registry.mint = data.mint;
+    oft_registry.oft_program = data.oft_program;
+    oft_registry.enabled = data.enabled;
+
+    msg!(
+        "OFT of mint {} updated by owner: {}. OFT program: {}, Enabled: {}",
+        data.mint,
+        ctx.accounts.owner.key(),
+        data.oft_program,
+        data.enabled
+    );
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use {
+    crate::{
+        bridge_in::ContractConfig,
+        bridge_out::{CommissionRecipient, PartnerConfig},
+    },
+    anchor_lang::prelude::*,
+};
+
+
+#[derive(Accounts)]
+#[instruction(data: SetPartnerParams)]
+pub struct SetPartnerContext<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [b"contract_config"],
+        bump
+    )]
+    pub contract_config: Account<'info, ContractConfig>,
+
+    // partnerConfig state account, one per partner id
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + PartnerConfig::INIT_SPACE,
+        seeds = [b"partner", data.partner_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub partner_config: Account<'info, PartnerConfig>,
+
+    /// System Program.
+    /// "11111111111111111111111111111111"
+    /// CHECK: fixed
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SetPartnerParams {
+    pub partner_id: u64,
+    pub recipients: Vec<CommissionRecipient>,
+    pub active: bool,
+}
+
+pub fn set_partner(ctx: Context<SetPartnerContext>, data: SetPartnerParams) -> Result<()> {
+    let total_rate = PartnerConfig::validate_recipients(&data.recipients)?;
+
+    let partner_config = &mut ctx.accounts.partner_config;
+    partner_config.partner_id = data.partner_id;
+    partner_config.recipients = data.recipients;
+    partner_config.active = data.active;
+
+    msg!(
+        "Partner {} updated by owner: {}. Recipients: {}, Total commission rate: {}, Active: {}",
+        data.partner_id,
+        ctx.accounts.owner.key(),
+        partner_config.recipients.len(),
+        total_rate,
+        data.active
+    );
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use {
+    crate::common::{XBridgeErrorCode, COMMISSION_DENOMINATOR, COMMISSION_RATE_LIMIT},
+    anchor_lang::prelude::*,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct CommissionRecipient {
+    pub recipient: Pubkey,          // SOL commission account, or owner of the commission token account
+    pub rate: u16,                  // Commission rate of this recipient
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PartnerConfig {
+    pub partner_id: u64,            // The partner / referrer id
+    #[max_len(MAX_COMMISSION_RECIPIENTS)]
+    pub recipients: Vec<CommissionRecipient>, // Payout order, combined rate capped by COMMISSION_RATE_LIMIT
+    pub active: bool,               // Inactive partners cannot be used for commission
+}
+
+pub const MAX_COMMISSION_RECIPIENTS: usize = 5;
+
+impl PartnerConfig {
+    pub fn validate_recipients(recipients: &[CommissionRecipient]) -> Result<u16> {
+        require!(
+            !recipients.is_empty() && recipients.len() <= MAX_COMMISSION_RECIPIENTS,
+            XBridgeErrorCode::InvalidCommissionAccount
+        );
+        let mut total_rate: u16 = 0;
+        for r in recipients {
+            require!(r.recipient != Pubkey::default(), XBridgeErrorCode::InvalidCommissionAccount);
+            require!(r.rate > 0, XBridgeErrorCode::InvalidCommissionRate);
+            total_rate = total_rate.checked_add(r.rate).ok_or(XBridgeErrorCode::InvalidCommissionRate)?;
+        }
+        require!(total_rate <= COMMISSION_RATE_LIMIT, XBridgeErrorCode::InvalidCommissionRate);
+        Ok(total_rate)
+    }
+
+    pub fn total_rate(&self) -> u16 {
+        self.recipients.iter().map(|r| r.rate).sum()
+    }
+
+    /// Commission of each recipient on top of the bridged amount,
+    /// amount * rate / (COMMISSION_DENOMINATOR - total_rate).
+    pub fn commission_amounts(&self, amount: u64) -> Result<Vec<u64>> {
+        let denominator = COMMISSION_DENOMINATOR
+            .checked_sub(self.total_rate() as u64)
+            .ok_or(XBridgeErrorCode::CalculationError)?;
+        self.recipients
+            .iter()
+            .map(|r| {
+                amount
+                    .checked_mul(r.rate as u64)
+                    .and_then(|v| v.checked_div(denominator))
+                    .ok_or(XBridgeErrorCode::CalculationError.into())
+            })
+            .collect()
+    }
+
+    /// Commission of each recipient on the amount consumed by the bridge, paid in a token priced at
+    /// price_in per price_out bridge token, consumed * rate * price_in / (COMMISSION_DENOMINATOR * price_out).
+    pub fn consumed_amounts(&self, consumed: u64, price_in: u64, price_out: u64) -> Result<Vec<u64>> {
+        require!(price_in > 0 && price_out > 0, XBridgeErrorCode::InvalidCommissionPrice);
+        let denominator = (COMMISSION_DENOMINATOR as u128) * (price_out as u128);
+        self.recipients
+            .iter()
+            .map(|r| {
+                (consumed as u128)
+                    .checked_mul(r.rate as u128)
+                    .and_then(|v| v.checked_mul(price_in as u128))
+                    .and_then(|v| u64::try_from(v / denominator).ok())
+                    .ok_or(XBridgeErrorCode::CalculationError.into())
+            })
+            .collect()
+    }
+
+    /// Commission of each recipient skimmed from an inbound source amount,
+    /// amount * rate / COMMISSION_DENOMINATOR.
+    pub fn skim_amounts(&self, amount: u64) -> Result<Vec<u64>> {
+        self.recipients
+            .iter()
+            .map(|r| {
+                amount
+                    .checked_mul(r.rate as u64)
+                    .and_then(|v| v.checked_div(COMMISSION_DENOMINATOR))
+                    .ok_or(XBridgeErrorCode::CalculationError.into())
+            })
+            .collect()
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ChainRegistry {
+    pub adaptor_id: u8,             // The bridge adaptor the native chain id belongs to
+    pub native_chain_id: u64,       // The bridge's own chain id, as passed in BridgeToArgs.to_chain_id
+    pub chain_id: u64,              // The canonical (EVM) chain id logged by bridge_to_log
+    pub enabled: bool,              // Disabled chains cannot be bridged to
+    pub to_len: u8,                 // Expected length of BridgeToArgs.to, 0 accepts any length, at most MAX_RECIPIENT_LEN
+}
+
+impl ChainRegistry {
+    pub fn validate_to(&self, to: &[u8]) -> Result<()> {
+        require!(
+            self.to_len == 0 || to.len() == self.to_len as usize,
+            XBridgeErrorCode::InvalidRecipientLength
+        );
+        Ok(())
+    }
+}
+
+pub const NTT_MODE_LOCKING: u8 = 0;
+pub const NTT_MODE_BURNING: u8 = 1;
+
+#[account]
+#[derive(InitSpace)]
+pub struct NttManagerRegistry {
+    pub mint: Pubkey,               // Token bridged through the NTT manager
+    pub manager: Pubkey,            // NTT manager program of the mint
+    pub transceiver: Pubkey,        // Wormhole transceiver program releasing the outbox items
+    pub mode: u8,                   // NTT_MODE_LOCKING or NTT_MODE_BURNING, as configured on the manager
+    pub enabled: bool,              // Disabled managers cannot be bridged through
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OftRegistry {
+    pub mint: Pubkey,               // Token sent through the OFT program
+    pub oft_program: Pubkey,        // OFT program of the mint
+    pub enabled: bool,              // Disabled OFTs cannot be bridged through
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn consumed_amounts() {
+        let partner_config = PartnerConfig {
+            partner_id: 1,
+            recipients: vec![
+                CommissionRecipient { recipient: Pubkey::new_unique(), rate: 100 },
+                CommissionRecipient { recipient: Pubkey::new_unique(), rate: 50 },
+            ],
+            active: true,
+        };
+        // no gross-up on the consumed amount
+        assert_eq!(partner_config.consumed_amounts(1_000_000, 1, 1).unwrap(), vec![10_000, 5_000]);
+        assert_eq!(partner_config.commission_amounts(1_000_000).unwrap(), vec![10_152, 5_076]);
+        // 1_000_000 bridge units quoted at 2_000_000 commission units
+        assert_eq!(partner_config.consumed_amounts(1_000_000, 2_000_000, 1_000_000).unwrap(), vec![20_000, 10_000]);
+        assert_eq!(partner_config.consumed_amounts(u64::MAX, u64::MAX, 1).unwrap_err(), XBridgeErrorCode::CalculationError.into());
+        assert!(partner_config.consumed_amounts(1_000_000, 1, 0).is_err());
+        assert!(partner_config.consumed_amounts(1_000_000, 0, 1).is_err());
+    }
+}
@@ -26,56 +26,38 @@ pub enum SwapType {
     SWAPANDBRIDGE,
 }
 
+/// Base and token of the partner commission. AMOUNT and CONSUMED are paid in the bridge mint,
+/// by splcommission from user_token_account and by solcommission in lamports of a WSOL mint.
+/// CONSUMEDINMINT is paid in the commission mint: by splcommission from commission_token_account,
+/// typically the output of the from-swap, and by solcommission in lamports for any bridge mint.
 #[derive(Debug, PartialEq, AnchorDeserialize, AnchorSerialize, Clone, Copy)]
-pub enum AdaptorID {
-    /* 00 */ Bridge0,
-    /* 01 */ Bridge1,
-    /* 02 */ Bridge2,
-    /* 03 */ Bridgers,
-    /* 04 */ Bridge4,
-    /* 05 */ Bridge5,
-    /* 06 */ Bridge6,
-    /* 07 */ Bridge7,
-    /* 08 */ Bridge8,
-    /* 09 */ Bridge9,
-    /* 10 */ Bridge10,
-    /* 11 */ Bridge11,
-    /* 12 */ Bridge12,
-    /* 13 */ Bridge13,
-    /* 14 */ Bridge14,
-    /* 15 */ Bridge15,
-    /* 16 */ Bridge16,
-    /* 17 */ Wanchain,
-    /* 18 */ Cctp,
-    /* 19 */ Bridge19,
-    /* 20 */ Bridge20,
-    /* 21 */ Wormhole,
-    /* 22 */ Meson,
-    /* 23 */ Bridge23,
-    /* 24 */ Bridge24,
-    /* 25 */ Bridge25,
-    /* 26 */ Bridge26,
-    /* 27 */ Bridge27,
-    /* 28 */ Bridge28,
-    /* 29 */ Bridge29,
-    /* 30 */ Bridge30,
-    /* 31 */ Bridge31,
-    /* 32 */ Bridge32,
-    /* 33 */ Bridge33,
-    /* 34 */ Debridgedln,
-    /* 35 */ Bridge35,
-    /* 36 */ Bridge36,
-    /* 37 */ Bridge37,
-    /* 38 */ Bridge38,
-    /* 39 */ Bridge39,
-    /* 40 */ Bridge40,
-    /* 41 */ Allbridge,
-    /* 42 */ Bridge42,
-    /* 43 */ Bridge43,
-    /* 44 */ Bridge44,
-    /* 45 */ Bridge45,
-    /* 46 */ Bridge46,
-    /* 47 */ MayanSwift,
-    /* 48 */ Bridge48,
-    /* 49 */ Bridge49,
+pub enum CommissionMode {
+    AMOUNT,     // on top of data.amount, paid before bridging
+    CONSUMED,   // consumed * rate / 10000 of the amount consumed by the bridge, paid after bridging
+    CONSUMEDINMINT {
+        price_in: u64,  // commission mint amount of the quote
+        price_out: u64, // bridge mint amount of the quote
+    },          // as CONSUMED, converted to the commission mint at price_in per price_out
+}
+
+/// Bridge adaptor id, wire compatible with the former one byte enum.
+/// Every id is routed to its BridgeAdaptor in bridge_to_log.
+#[derive(Debug, PartialEq, Eq, AnchorDeserialize, AnchorSerialize, Clone, Copy)]
+pub struct AdaptorID(pub u8);
+
+impl AdaptorID {
+    pub const BRIDGERS: AdaptorID = AdaptorID(3);
+    pub const WANCHAIN: AdaptorID = AdaptorID(17);
+    pub const CCTP: AdaptorID = AdaptorID(18);
+    pub const WORMHOLE: AdaptorID = AdaptorID(21);
+    pub const MESON: AdaptorID = AdaptorID(22);
+    pub const DEBRIDGEDLN: AdaptorID = AdaptorID(34);
+    pub const ALLBRIDGE: AdaptorID = AdaptorID(41);
+    pub const MAYAN_SWIFT: AdaptorID = AdaptorID(47);
+    pub const CCTP_V2: AdaptorID = AdaptorID(48);
+    pub const WORMHOLE_NTT: AdaptorID = AdaptorID(49);
+    pub const LAYERZERO_OFT: AdaptorID = AdaptorID(50);
+    pub const ACROSS: AdaptorID = AdaptorID(51);
+    pub const MAYAN_MCTP: AdaptorID = AdaptorID(52);
+    pub const MAYAN_WH_SWAP: AdaptorID = AdaptorID(53);
 }
@@ -8,6 +8,15 @@ pub const TEST_MPC_STR: &str = "4JytEnivUZr9wVCsENs6dcWvzjYQF41RPkjQxwppo2j6";
 pub const TEST_MPC_STR_2: &str = "5UYLAV5znKESoEoZT7orPGC5BmDtB5YsXwhFshhLqyeC";
 pub const COMMISSION_RATE_LIMIT: u16 = 300;
 pub const COMMISSION_DENOMINATOR: u64 = 10000;
+pub const BPS_DENOMINATOR: u64 = 10000;
+pub const SELF_REFUND_MIN_DELAY: i64 = 24 * 60 * 60;
+// virtual shares and liquidity added to both sides of the LP share price
+pub const LP_VIRTUAL_LIQUIDITY: u64 = 1_000_000;
+pub const SELF_REFUND_MAX_DELAY: i64 = 30 * 24 * 60 * 60;
+// longest recipient a chain registry can require, e.g. a bech32 address as utf-8 bytes
+pub const MAX_RECIPIENT_LEN: u8 = 64;
+pub const CCTP_SOLANA_DOMAIN: u32 = 5;
+pub const WORMHOLE_SOLANA_CHAIN: u16 = 1;
 
 pub mod dexrouter_program {
     crate::declare_id!("6m2CDdhRgxpH4WjvdzxAYbGxwdGUz5MziiL5jek2kBma");
@@ -24,6 +33,10 @@ pub mod wormhole_token_bridge_program {
     crate::declare_id!("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb");
 }
 
+pub mod wormhole_token_bridge_relayer_program {
+    crate::declare_id!("3bPRWXqtSfUaCw3S4wdgvypQtsSzcmvDeaqSqPDkiBXB");
+}
+
 pub mod meson_program {
     crate::declare_id!("FR1SDyLUj7PrMbtkUCkDrBymk5eWrRmr3UvWFb5Kjbmd");
 }
@@ -44,6 +57,22 @@ pub mod cctp_message_program {
     crate::declare_id!("CCTPmbSD7gX1bxKPAmg77w8oFzNFpaQiQUWD43TKaecd");
 }
 
+pub mod cctp_v2_program {
+    crate::declare_id!("CCTPV2vPZJS2u2BBsUoscuikbYjnpFmbFsvVuJdgUMQe");
+}
+
+pub mod cctp_v2_message_program {
+    crate::declare_id!("CCTPV2Sm4AdWt5296sk4P1tnNmRvzJ3CiEodH5uSFe9K");
+}
+
+pub mod layerzero_endpoint_program {
+    crate::declare_id!("76y77prsiCMvXMjuoZ5VRrhG5qYBrUMYTE5WgHqgjEn6");
+}
+
+pub mod across_spoke_pool_program {
+    crate::declare_id!("DLv3NggMiSaef97YCkew5xKUHDh13tVGZ7tydt3ZeAru");
+}
+
 pub mod allbridge_program {          
     use anchor_lang::declare_id;
     declare_id!("BrdgN2RPzEMWF96ZbnnJaUtQDQx7VRXYaHHbYCBvceWB");
@@ -87,6 +116,14 @@ pub mod mayan_swift_program {
     crate::declare_id!("BLZRi6frs4X4DNLw56V4EXai1b6QVESN1BhHBTYM9VcY");
 }
 
+pub mod mayan_mctp_program {
+    crate::declare_id!("dkpZqrxHFrhziEMQ931GLtfy11nFkCsfMftH9u6QwBU");
+}
+
+pub mod mayan_swap_program {
+    crate::declare_id!("FC4eXxkyrMPTjiYUpp4EAnkmwMbQyZCRNhdtxcGP3pFS");
+}
+
 pub mod mayan_fee_manager_program {
     crate::declare_id!("5VtQHnhs2pfVEr68qQsbTRwKh4JV5GTu9mBHgHFxpHeQ");
 }
@@ -103,6 +103,93 @@ pub enum XBridgeErrorCode {
 
     #[msg("Wsol Pda Failed To Decode TokenAccount")]
     WsolPdaFailedToDecodeTokenAccount,
+
+    #[msg("Destination mint does not match the signed message")]
+    DestinationMintMismatch,
+
+    #[msg("Invalid gasrefund account")]
+    InvalidGasRefundAccount,
+
+    #[msg("Gas refund fee exceeds cap")]
+    GasRefundFeeExceedsCap,
+
+    #[msg("Invalid gas refund fee cap")]
+    InvalidGasRefundFeeCap,
+
+    #[msg("Invalid self refund delay")]
+    InvalidSelfRefundDelay,
+
+    #[msg("Self refund delay not reached")]
+    SelfRefundDelayNotReached,
+
+    #[msg("Invalid liquidity amount")]
+    InvalidLiquidityAmount,
+
+    #[msg("Insufficient vault liquidity")]
+    InsufficientVaultLiquidity,
+
+    #[msg("Invalid lp fee")]
+    InvalidLpFee,
+
+    #[msg("Partner not active")]
+    PartnerNotActive,
+
+    #[msg("Invalid commission account")]
+    InvalidCommissionAccount,
+
+    #[msg("Chain not enabled")]
+    ChainNotEnabled,
+
+    #[msg("Invalid recipient address length")]
+    InvalidRecipientLength,
+
+    #[msg("Invalid finality threshold")]
+    InvalidFinalityThreshold,
+
+    #[msg("Invalid max fee")]
+    InvalidMaxFee,
+
+    #[msg("Invalid cctp message")]
+    InvalidCctpMessage,
+
+    #[msg("Cctp destination caller mismatch")]
+    CctpDestinationCallerMismatch,
+
+    #[msg("Invalid swap payload")]
+    InvalidSwapPayload,
+
+    #[msg("Invalid wormhole vaa")]
+    InvalidWormholeVaa,
+
+    #[msg("Invalid relayer fee")]
+    InvalidRelayerFee,
+
+    #[msg("Invalid ntt mode")]
+    InvalidNttMode,
+
+    #[msg("Ntt manager not enabled")]
+    NttManagerNotEnabled,
+
+    #[msg("Ntt manager mismatch")]
+    NttManagerMismatch,
+
+    #[msg("Oft not enabled")]
+    OftNotEnabled,
+
+    #[msg("Invalid min amount")]
+    InvalidMinAmount,
+
+    #[msg("Invalid fill deadline")]
+    InvalidFillDeadline,
+
+    #[msg("Commission mint requires the commission price")]
+    InvalidCommissionMint,
+
+    #[msg("Chain registry required by the adaptor")]
+    ChainRegistryRequired,
+
+    #[msg("Invalid commission price")]
+    InvalidCommissionPrice,
 }
 
 #[error_code]
@@ -1,7 +1,7 @@
 use {
     tiny_keccak::{Hasher, Keccak},
     hex::encode,
-    anchor_lang::prelude::*,
+    anchor_lang::{prelude::*, system_program},
     crate::common::XBridgeErrorCode,
 };
 
@@ -32,3 +32,70 @@ pub fn safe_to_fixed_bytes<const N: usize>(data: Vec<u8>) -> Result<[u8; N]> {
     fixed[..len].copy_from_slice(&data[..len]);
     Ok(fixed)
 }
+
+/// Creates the PDA `account` with `space` bytes owned by `owner` if it does not exist yet,
+/// otherwise grows it to `space` bytes. Returns true when the account was created.
+pub fn init_or_realloc_pda<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> Result<bool> {
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    let top_up = required_lamports.saturating_sub(account.lamports());
+    let is_new = account.data_is_empty() && account.owner == &system_program::ID;
+    if !is_new {
+        require_keys_eq!(*account.owner, *owner, ErrorCode::AccountOwnedByWrongProgram);
+        if account.data_len() >= space {
+            return Ok(false);
+        }
+    }
+
+    if is_new && account.lamports() == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount { from: payer.clone(), to: account.clone() },
+                &[signer_seeds],
+            ),
+            required_lamports,
+            space as u64,
+            owner,
+        )?;
+        return Ok(true);
+    }
+
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer { from: payer.clone(), to: account.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    if is_new {
+        // the address was funded before, allocate and assign instead of create_account
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate { account_to_allocate: account.clone() },
+                &[signer_seeds],
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Assign { account_to_assign: account.clone() },
+                &[signer_seeds],
+            ),
+            owner,
+        )?;
+    } else {
+        account.realloc(space, true)?;
+    }
+    Ok(is_new)
+}
@@ -43,6 +43,24 @@ pub mod web3_bridge_v2 {
         bridge_in::set_oracle(ctx, data)
     }
 
+    pub fn set_gasrefund(ctx: Context<SetGasRefundContext>, data: SetGasRefundParams) -> Result<()> {
+        bridge_in::set_gasrefund(ctx, data)
+    }
+
+    pub fn set_gasrefund_fee_cap(
+        ctx: Context<SetGasRefundFeeCapContext>,
+        data: SetGasRefundFeeCapParams,
+    ) -> Result<()> {
+        bridge_in::set_gasrefund_fee_cap(ctx, data)
+    }
+
+    pub fn set_self_refund_delay(
+        ctx: Context<SetSelfRefundDelayContext>,
+        data: SetSelfRefundDelayParams,
+    ) -> Result<()> {
+        bridge_in::set_self_refund_delay(ctx, data)
+    }
+
     pub fn pause(ctx: Context<PauseContext>) -> Result<()> {
         bridge_in::pause(ctx)
     }
@@ -51,6 +69,36 @@ pub mod web3_bridge_v2 {
         bridge_in::unpause(ctx)
     }
 
+    pub fn create_vault(ctx: Context<CreateVaultContext>) -> Result<()> {
+        bridge_in::create_vault(ctx)
+    }
+
+    pub fn deposit_liquidity(
+        ctx: Context<DepositLiquidityContext>,
+        data: DepositLiquidityParams,
+    ) -> Result<()> {
+        bridge_in::deposit_liquidity(ctx, data)
+    }
+
+    pub fn withdraw_liquidity(
+        ctx: Context<WithdrawLiquidityContext>,
+        data: WithdrawLiquidityParams,
+    ) -> Result<()> {
+        bridge_in::withdraw_liquidity(ctx, data)
+    }
+
+    pub fn set_lp_fee(ctx: Context<SetLpFeeContext>, data: SetLpFeeParams) -> Result<()> {
+        bridge_in::set_lp_fee(ctx, data)
+    }
+
+    pub fn lp_deposit(ctx: Context<LpDeposit>, data: LpDepositArgs) -> Result<()> {
+        bridge_in::lp_deposit(ctx, data)
+    }
+
+    pub fn lp_withdraw(ctx: Context<LpWithdraw>, data: LpWithdrawArgs) -> Result<()> {
+        bridge_in::lp_withdraw(ctx, data)
+    }
+
     pub fn verify<'info>(
         _ctx: Context<'_, '_, '_, 'info, Verify<'info>>,
         data: VerifyArgs,
@@ -59,19 +107,33 @@ pub mod web3_bridge_v2 {
     }
     
     pub fn claim<'info>(
-        _ctx: Context<'_, '_, '_, 'info, Claim<'info>>, 
+        _ctx: Context<'_, '_, 'info, 'info, Claim<'info>>, 
         data: ClaimArgs,
     ) -> Result<()> {
         bridge_in::claim(_ctx, data)
     }
 
     pub fn claim_to_sol<'info>(
-        _ctx: Context<'_, '_, '_, 'info, Claim<'info>>, 
+        _ctx: Context<'_, '_, 'info, 'info, Claim<'info>>, 
         data: ClaimArgs,
     ) -> Result<()> {
         bridge_in::claim_to_sol(_ctx, data)
     }
 
+    pub fn receive_cctp<'info>(
+        _ctx: Context<'_, '_, '_, 'info, ReceiveCctp<'info>>,
+        data: ReceiveCctpArgs,
+    ) -> Result<()> {
+        bridge_in::receive_cctp(_ctx, data)
+    }
+
+    pub fn redeem_wormhole<'info>(
+        _ctx: Context<'_, '_, '_, 'info, RedeemWormhole<'info>>,
+        data: RedeemWormholeArgs,
+    ) -> Result<()> {
+        bridge_in::redeem_wormhole(_ctx, data)
+    }
+
     pub fn refund<'info>(
         _ctx: Context<'_, '_, '_, 'info, Refund<'info>>, 
         data: RefundArgs, 
@@ -79,6 +141,23 @@ pub mod web3_bridge_v2 {
         bridge_in::refund(_ctx, data)
     }
 
+    pub fn self_refund<'info>(
+        _ctx: Context<'_, '_, '_, 'info, SelfRefund<'info>>, 
+        data: SelfRefundArgs, 
+    ) -> Result<()> {
+        bridge_in::self_refund(_ctx, data)
+    }
+
+    pub fn migrate_message_state(ctx: Context<MigrateMessageState>) -> Result<()> {
+        bridge_in::migrate_message_state(ctx)
+    }
+
+    pub fn report_reserves<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReportReserves<'info>>,
+    ) -> Result<()> {
+        bridge_in::report_reserves(ctx)
+    }
+
     // bridge_out
     pub fn bridge_to_log<'info>(
         ctx: Context<'_, '_, '_, 'info, BridgeTo<'info>>,
@@ -89,7 +168,7 @@ pub mod web3_bridge_v2 {
 
     // bridge_out + spl commission  cctp、wormhole
     pub fn bridge_to_log_splcommission<'info>(
-        ctx: Context<'_, '_, '_, 'info, BridgeToSplCommission<'info>>,
+        ctx: Context<'_, '_, 'info, 'info, BridgeToSplCommission<'info>>,
         data: BridgeToCommissionArgs,
     ) -> Result<()> {
         bridge_out::bridge_to_log_splcommission(ctx, data)
@@ -103,4 +182,20 @@ pub mod web3_bridge_v2 {
         bridge_out::bridge_to_log_solcommission(ctx, data)
     }
 
+    pub fn set_partner(ctx: Context<SetPartnerContext>, data: SetPartnerParams) -> Result<()> {
+        bridge_out::set_partner(ctx, data)
+    }
+
+    pub fn set_chain(ctx: Context<SetChainContext>, data: SetChainParams) -> Result<()> {
+        bridge_out::set_chain(ctx, data)
+    }
+
+    pub fn set_ntt_manager(ctx: Context<SetNttManagerContext>, data: SetNttManagerParams) -> Result<()> {
+        bridge_out::set_ntt_manager(ctx, data)
+    }
+
+    pub fn set_oft(ctx: Context<SetOftContext>, data: SetOftParams) -> Result<()> {
+        bridge_out::set_oft(ctx, data)
+    }
+
 }
\ No newline at end of file